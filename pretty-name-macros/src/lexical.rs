@@ -0,0 +1,192 @@
+//! Detection of types whose names can be determined from their tokens alone.
+//!
+//! A path in the tokens of a type may be a type alias or a renamed import, whose name
+//! differs from the name of the type it resolves to. A procedural macro cannot resolve
+//! paths, so paths into the standard library are only named from their tokens if they
+//! are known to be the canonical location of a type or trait, and single identifiers
+//! naming well-known standard library items are checked by the caller to resolve to
+//! them. Other paths are assumed to name the user's own types. Everything else, like
+//! `Self` or `std::num::NonZeroU32`, which is an alias of `NonZero<u32>`, is named at
+//! runtime instead.
+
+use syn::*;
+
+/// Primitive types, which are spelled canonically through `core::primitive`.
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "str",
+    "i8", "i16", "i32", "i64", "i128", "isize",
+    "u8", "u16", "u32", "u64", "u128", "usize",
+    "f32", "f64",
+];
+
+/// Standard library types and the modules of `std` that define or re-export them. The
+/// first module is the canonical spelling of the type named by a single identifier.
+const TYPES: &[(&str, &[&[&str]])] = &[
+    ("Arc", &[&["sync"]]),
+    ("BTreeMap", &[&["collections"], &["collections", "btree_map"]]),
+    ("BTreeSet", &[&["collections"], &["collections", "btree_set"]]),
+    ("BinaryHeap", &[&["collections"], &["collections", "binary_heap"]]),
+    ("Box", &[&["boxed"]]),
+    ("CStr", &[&["ffi"]]),
+    ("CString", &[&["ffi"]]),
+    ("Cell", &[&["cell"]]),
+    ("Cow", &[&["borrow"]]),
+    ("Duration", &[&["time"]]),
+    ("HashMap", &[&["collections"], &["collections", "hash_map"]]),
+    ("HashSet", &[&["collections"], &["collections", "hash_set"]]),
+    ("Instant", &[&["time"]]),
+    ("LinkedList", &[&["collections"], &["collections", "linked_list"]]),
+    ("ManuallyDrop", &[&["mem"]]),
+    ("MaybeUninit", &[&["mem"]]),
+    ("Mutex", &[&["sync"]]),
+    ("NonNull", &[&["ptr"]]),
+    ("NonZero", &[&["num"]]),
+    ("Option", &[&["option"]]),
+    ("OsStr", &[&["ffi"]]),
+    ("OsString", &[&["ffi"]]),
+    ("Path", &[&["path"]]),
+    ("PathBuf", &[&["path"]]),
+    ("PhantomData", &[&["marker"]]),
+    ("Pin", &[&["pin"]]),
+    ("Range", &[&["ops"]]),
+    ("RangeInclusive", &[&["ops"]]),
+    ("Rc", &[&["rc"]]),
+    ("RefCell", &[&["cell"]]),
+    ("Result", &[&["result"]]),
+    ("RwLock", &[&["sync"]]),
+    ("String", &[&["string"]]),
+    ("SystemTime", &[&["time"]]),
+    ("Vec", &[&["vec"]]),
+    ("VecDeque", &[&["collections"], &["collections", "vec_deque"]]),
+    ("Wrapping", &[&["num"]]),
+];
+
+/// Standard library traits and the modules of `std` that define or re-export them, like
+/// [`TYPES`].
+const TRAITS: &[(&str, &[&[&str]])] = &[
+    ("Any", &[&["any"]]),
+    ("AsMut", &[&["convert"]]),
+    ("AsRef", &[&["convert"]]),
+    ("Debug", &[&["fmt"]]),
+    ("Display", &[&["fmt"]]),
+    ("Error", &[&["error"]]),
+    ("Fn", &[&["ops"]]),
+    ("FnMut", &[&["ops"]]),
+    ("FnOnce", &[&["ops"]]),
+    ("Future", &[&["future"]]),
+    ("IntoIterator", &[&["iter"]]),
+    ("Iterator", &[&["iter"]]),
+    ("Send", &[&["marker"]]),
+    ("Sync", &[&["marker"]]),
+    ("Unpin", &[&["marker"]]),
+];
+
+/// Build the canonical spelling of the given type, in which every single identifier
+/// naming a standard library item is spelled by its full path, so that the caller can
+/// check that it is not shadowed by a type alias or a renamed import.
+///
+/// Returns `None` for types whose names differ from their tokens or cannot be proven
+/// not to: types mentioning `Self`, qualified paths like `<T as Trait>::Assoc`, `impl
+/// Trait`, macros, inferred types, non-literal array lengths, and paths into the
+/// standard library other than the known locations of its types and traits.
+pub fn canonical_type(ty: &Type) -> Option<Type> {
+    let mut ty = ty.clone();
+    canonicalize_type(&mut ty).then_some(ty)
+}
+
+fn canonicalize_type(ty: &mut Type) -> bool {
+    match *ty {
+        Type::Never(_) => true,
+
+        Type::Array(TypeArray { ref mut elem, ref len, .. }) => {
+            matches!(*len, Expr::Lit(ExprLit { lit: Lit::Int(_), .. })) && canonicalize_type(elem)
+        }
+
+        Type::Group(TypeGroup { ref mut elem, .. }) |
+        Type::Paren(TypeParen { ref mut elem, .. }) |
+        Type::Ptr(TypePtr { ref mut elem, .. }) |
+        Type::Reference(TypeReference { ref mut elem, .. }) |
+        Type::Slice(TypeSlice { ref mut elem, .. }) => canonicalize_type(elem),
+
+        Type::Path(ref mut ty) if ty.qself.is_none() => canonicalize_path(&mut ty.path, TYPES),
+
+        Type::BareFn(ref mut ty) => {
+            ty.inputs.iter_mut().all(|input| canonicalize_type(&mut input.ty)) &&
+            canonicalize_return_type(&mut ty.output)
+        }
+
+        Type::TraitObject(ref mut ty) => ty.bounds.iter_mut().all(|bound| match *bound {
+            TypeParamBound::Trait(ref mut trt) => canonicalize_path(&mut trt.path, TRAITS),
+            TypeParamBound::Lifetime(_) => true,
+            _ => false,
+        }),
+
+        Type::Tuple(ref mut ty) => ty.elems.iter_mut().all(canonicalize_type),
+
+        _ => false,
+    }
+}
+
+fn canonicalize_return_type(output: &mut ReturnType) -> bool {
+    match *output {
+        ReturnType::Default => true,
+        ReturnType::Type(_, ref mut ty) => canonicalize_type(ty),
+    }
+}
+
+fn canonicalize_path(path: &mut Path, known: &[(&str, &[&[&str]])]) -> bool {
+    for segment in path.segments.iter_mut() {
+        let canonical = match segment.arguments {
+            PathArguments::None => true,
+            PathArguments::AngleBracketed(ref mut args) => args.args.iter_mut().all(|arg| match *arg {
+                GenericArgument::Lifetime(_) => true,
+                GenericArgument::Type(ref mut ty) => canonicalize_type(ty),
+                GenericArgument::AssocType(ref mut ty) => canonicalize_type(&mut ty.ty),
+                _ => false,
+            }),
+            PathArguments::Parenthesized(ref mut args) => {
+                args.inputs.iter_mut().all(canonicalize_type) &&
+                canonicalize_return_type(&mut args.output)
+            }
+        };
+        if !canonical {
+            return false;
+        }
+    }
+
+    let first = &path.segments[0].ident;
+    if first == "Self" {
+        return false;
+    }
+
+    if path.segments.len() == 1 && path.leading_colon.is_none() {
+        let module = if PRIMITIVES.iter().any(|primitive| first == primitive) {
+            vec!["core", "primitive"]
+        } else if let Some(&(_, modules)) = known.iter().find(|&&(name, _)| first == name) {
+            [&["std"][..], modules[0]].concat()
+        } else {
+            // the user's own type
+            return true;
+        };
+        let span = first.span();
+        path.leading_colon = Some(Default::default());
+        path.segments = module
+            .into_iter()
+            .map(|name| PathSegment::from(Ident::new(name, span)))
+            .chain(path.segments.pop().map(|pair| pair.into_value()))
+            .collect();
+        return true;
+    }
+
+    if !["std", "core", "alloc"].iter().any(|name| first == name) {
+        // a path into the user's own code or another crate
+        return true;
+    }
+    let names = path.segments.iter().skip(1).map(|segment| segment.ident.to_string()).collect::<Vec<_>>();
+    let Some((last, modules)) = names.split_last() else {
+        return false;
+    };
+    known.iter().any(|&(name, known_modules)| {
+        last == name && known_modules.iter().any(|&known_module| known_module == modules)
+    })
+}
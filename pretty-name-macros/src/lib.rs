@@ -0,0 +1,245 @@
+//! Procedural macros for the `pretty-name` crate.
+//!
+//! This crate is an implementation detail of `pretty-name` and should not be used
+//! directly. Use the `const` forms of the naming macros of `pretty-name` instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::*;
+
+use pretty_name_syntax::{print, truncate};
+
+mod lexical;
+
+/// Input of `lexical_member_name!`: a member path like `Type::member`,
+/// `Type::<T>::member::<U>` or `<Type>::member`, optionally followed by the `(..)` or
+/// `{..}` of an enum variant.
+struct MemberPath {
+    ty: Type,
+    member: TokenTree,
+    args: Vec<Type>,
+}
+
+impl Parse for MemberPath {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty = if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            let ty = input.parse()?;
+            input.parse::<Token![>]>()?;
+            ty
+        } else {
+            let ident = input.call(Ident::parse_any)?;
+            let arguments = if is_turbofish(input) {
+                PathArguments::AngleBracketed(
+                    AngleBracketedGenericArguments::parse_turbofish(input)?)
+            } else {
+                PathArguments::None
+            };
+            Type::Path(TypePath {
+                qself: None,
+                path: PathSegment { ident, arguments }.into(),
+            })
+        };
+        input.parse::<Token![::]>()?;
+        let member = input.parse()?;
+        let mut args = Vec::new();
+        if is_turbofish(input) {
+            for arg in AngleBracketedGenericArguments::parse_turbofish(input)?.args {
+                match arg {
+                    GenericArgument::Type(ty) => args.push(ty),
+                    arg => return Err(Error::new_spanned(arg, "expected a type")),
+                }
+            }
+        }
+        // the `(..)` or `{..}` of an enum variant
+        input.parse::<proc_macro2::TokenStream>()?;
+        Ok(Self { ty, member, args })
+    }
+}
+
+fn is_turbofish(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<Token![::]>().is_ok() && fork.peek(Token![<])
+}
+
+/// Input of the `lexical_*_name!` macros: the tokens to name, and after a `;` the
+/// invocation of the runtime form of the naming macro.
+struct Lexical<T> {
+    input: Option<T>,
+    runtime: proc_macro2::TokenStream,
+}
+
+impl<T: Parse> Parse for Lexical<T> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut tokens = proc_macro2::TokenStream::new();
+        while !input.peek(Token![;]) {
+            tokens.extend([input.parse::<TokenTree>()?]);
+        }
+        input.parse::<Token![;]>()?;
+        Ok(Self {
+            input: parse2(tokens).ok(),
+            runtime: input.parse()?,
+        })
+    }
+}
+
+/// Expand to the truncated name of the given type as a string literal, or to the
+/// runtime form if the name cannot be determined from the tokens alone.
+#[doc(hidden)]
+#[proc_macro]
+pub fn lexical_type_name(input: TokenStream) -> TokenStream {
+    let Lexical { input, runtime } = parse_macro_input!(input as Lexical<Type>);
+    let Some(ty) = input else {
+        return runtime.into();
+    };
+    match check_canonical([&ty], &runtime) {
+        Some(check) => {
+            let name = lexical_name(&ty);
+            quote!({ #check #name }).into()
+        }
+        None => runtime.into(),
+    }
+}
+
+/// Expand to the canonical name of the given member like `Type::member` as a string
+/// literal, or to the runtime form if the name cannot be determined from the tokens
+/// alone.
+#[doc(hidden)]
+#[proc_macro]
+pub fn lexical_member_name(input: TokenStream) -> TokenStream {
+    let Lexical { input, runtime } = parse_macro_input!(input as Lexical<MemberPath>);
+    let Some(MemberPath { ty, member, args }) = input else {
+        return runtime.into();
+    };
+    match check_canonical(std::iter::once(&ty).chain(&args), &runtime) {
+        Some(check) => {
+            let mut name = truncate::member_name(&lexical_name(&ty), &member.to_string());
+            if !args.is_empty() {
+                let arg_names = args.iter().map(lexical_name).collect::<Vec<_>>();
+                name = format!("{}::<{}>", name, arg_names.join(", "));
+            }
+            quote!({ #check #name }).into()
+        }
+        None => runtime.into(),
+    }
+}
+
+/// Expand to a check that the given path names a trait, by using it as a bound.
+///
+/// Nested items cannot use the generic parameters of the surrounding function, so the
+/// generic arguments of the path are replaced with parameters of the item and checked
+/// in the surrounding scope instead. The item is a type alias, whose bounds are resolved
+/// but not checked to be well-formed, so the parameters need no bounds of their own.
+#[doc(hidden)]
+#[proc_macro]
+pub fn check_trait(input: TokenStream) -> TokenStream {
+    let mut path = parse_macro_input!(input as TraitBound).path;
+    let mut lifetimes = Vec::new();
+    let mut types = Vec::new();
+    for segment in &mut path.segments {
+        match &mut segment.arguments {
+            PathArguments::None => {}
+            PathArguments::AngleBracketed(arguments) => {
+                for arg in &mut arguments.args {
+                    match arg {
+                        GenericArgument::Lifetime(lifetime) => {
+                            *lifetime = replace_lifetime(lifetime, &mut lifetimes);
+                        }
+                        GenericArgument::Type(ty) | GenericArgument::AssocType(AssocType { ty, .. }) => {
+                            *ty = replace_type(ty, &mut types);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            PathArguments::Parenthesized(arguments) => {
+                for ty in &mut arguments.inputs {
+                    *ty = replace_type(ty, &mut types);
+                }
+                if let ReturnType::Type(_, ty) = &mut arguments.output {
+                    **ty = replace_type(ty, &mut types);
+                }
+            }
+        }
+    }
+    let (lifetime_params, lifetime_args): (Vec<_>, Vec<_>) = lifetimes.into_iter().unzip();
+    let (type_params, type_args): (Vec<_>, Vec<_>) = types.into_iter().unzip();
+    quote!({
+        #[allow(dead_code, type_alias_bounds)]
+        type __PrettyNameCheck<#(#lifetime_params,)* __PrettyNameSelf: ?Sized + #path, #(#type_params: ?Sized),*> = (
+            ::core::marker::PhantomData<__PrettyNameSelf>,
+            #(::core::marker::PhantomData<&#lifetime_params ()>,)*
+            #(::core::marker::PhantomData<#type_params>,)*
+        );
+        #(let _: ::core::marker::PhantomData<&#lifetime_args ()>;)*
+        #(let _: ::core::marker::PhantomData<#type_args>;)*
+    })
+    .into()
+}
+
+fn replace_lifetime(lifetime: &Lifetime, lifetimes: &mut Vec<(Lifetime, Lifetime)>) -> Lifetime {
+    let param = Lifetime::new(&format!("'__pretty_name_{}", lifetimes.len()), lifetime.span());
+    lifetimes.push((param.clone(), lifetime.clone()));
+    param
+}
+
+fn replace_type(ty: &Type, types: &mut Vec<(Ident, Type)>) -> Type {
+    let param = Ident::new(&format!("__PrettyNameArg{}", types.len()), proc_macro2::Span::call_site());
+    types.push((param.clone(), ty.clone()));
+    parse_quote!(#param)
+}
+
+/// Build an item that validates the input with the runtime form of the naming macro,
+/// and fails to compile unless every given type equals its canonical spelling, which
+/// rejects type aliases and renamed imports named like a standard library item.
+///
+/// The item is nested, so generic parameters of the surrounding item are rejected as
+/// well, whose names are not known from the tokens either.
+fn check_canonical<'a>(
+    types: impl IntoIterator<Item = &'a Type>,
+    runtime: &proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    let (types, canonical): (Vec<_>, Vec<_>) = types
+        .into_iter()
+        .map(|ty| Some((ty, lexical::canonical_type(ty)?)))
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    Some(quote! {
+        const _: () = {
+            let _ = || #runtime;
+            #(let _: ::core::marker::PhantomData<#types> =
+                ::core::marker::PhantomData::<#canonical>;)*
+        };
+    })
+}
+
+fn lexical_name(ty: &Type) -> String {
+    let mut ty = ty.clone();
+    truncate::truncate_type(&mut ty, false);
+    let mut name = String::new();
+    print::print_type(&ty, &mut name);
+    name
+}
+
+/// Register the type in `pretty_name::registry` before the first lookup.
+///
+/// Generic types cannot be registered automatically, since the registry stores concrete
+/// types only. Register their instantiations with `pretty_name::registry::register`.
+#[proc_macro_derive(PrettyName)]
+pub fn derive_pretty_name(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    if !input.generics.params.is_empty() {
+        return TokenStream::new();
+    }
+    let ident = input.ident;
+    quote! {
+        ::pretty_name::__inventory::submit! {
+            ::pretty_name::registry::Registration::of::<#ident>()
+        }
+    }
+    .into()
+}
@@ -0,0 +1,9 @@
+//! Type name truncation and printing shared by the `pretty-name` crate and the
+//! `pretty-name-macros` crate, so that names built from tokens at compile time follow
+//! exactly the same rules as names built at runtime.
+//!
+//! This crate is an implementation detail of `pretty-name` and should not be used
+//! directly.
+
+pub mod print;
+pub mod truncate;
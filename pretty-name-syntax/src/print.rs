@@ -0,0 +1,377 @@
+//! Printing of types on a single line in the layout rustfmt uses.
+
+use quote::ToTokens;
+use syn::*;
+
+/// Kind of a piece of printed text, for highlighting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Path,
+    Primitive,
+    Lifetime,
+    Keyword,
+    Literal,
+    Punct,
+    Whitespace,
+}
+
+/// Destination of [`print_type`].
+pub trait Output {
+    /// Append a piece of text of the given kind.
+    fn emit(&mut self, kind: Kind, text: &str);
+}
+
+impl Output for String {
+    fn emit(&mut self, _: Kind, text: &str) {
+        self.push_str(text);
+    }
+}
+
+/// Print the given type to `out`.
+pub fn print_type(ty: &Type, out: &mut impl Output) {
+    match *ty {
+        Type::Array(TypeArray { ref elem, ref len, .. }) => {
+            out.emit(Kind::Punct, "[");
+            print_type(elem, out);
+            out.emit(Kind::Punct, ";");
+            out.emit(Kind::Whitespace, " ");
+            match *len {
+                Expr::Lit(ExprLit { lit: Lit::Int(ref lit), .. }) => {
+                    out.emit(Kind::Literal, lit.base10_digits())
+                }
+                ref len => print_expr(len, out),
+            }
+            out.emit(Kind::Punct, "]");
+        }
+
+        Type::BareFn(ref ty) => {
+            print_bound_lifetimes(&ty.lifetimes, out);
+            if ty.unsafety.is_some() {
+                keyword("unsafe", out);
+            }
+            if let Some(ref abi) = ty.abi {
+                // rustfmt spells out the default ABI of a bare `extern`.
+                keyword("extern", out);
+                match abi.name {
+                    Some(ref name) => out.emit(Kind::Literal, &name.to_token_stream().to_string()),
+                    None => out.emit(Kind::Literal, "\"C\""),
+                }
+                out.emit(Kind::Whitespace, " ");
+            }
+            out.emit(Kind::Keyword, "fn");
+            out.emit(Kind::Punct, "(");
+            for (i, input) in ty.inputs.iter().enumerate() {
+                if i > 0 {
+                    comma(out);
+                }
+                if let Some((ref name, _)) = input.name {
+                    out.emit(Kind::Path, &name.to_string());
+                    out.emit(Kind::Punct, ":");
+                    out.emit(Kind::Whitespace, " ");
+                }
+                print_type(&input.ty, out);
+            }
+            if ty.variadic.is_some() {
+                if !ty.inputs.is_empty() {
+                    comma(out);
+                }
+                out.emit(Kind::Punct, "...");
+            }
+            out.emit(Kind::Punct, ")");
+            print_return_type(&ty.output, out);
+        }
+
+        Type::Group(TypeGroup { ref elem, .. }) => print_type(elem, out),
+
+        Type::ImplTrait(TypeImplTrait { ref bounds, .. }) => {
+            keyword("impl", out);
+            print_bounds(bounds.iter(), out);
+        }
+
+        Type::Infer(_) => out.emit(Kind::Path, "_"),
+
+        Type::Never(_) => out.emit(Kind::Punct, "!"),
+
+        Type::Paren(TypeParen { ref elem, .. }) => {
+            out.emit(Kind::Punct, "(");
+            print_type(elem, out);
+            out.emit(Kind::Punct, ")");
+        }
+
+        Type::Path(TypePath { ref qself, ref path }) => match *qself {
+            None => print_path(path, false, out),
+            Some(ref qself) => {
+                out.emit(Kind::Punct, "<");
+                print_type(&qself.ty, out);
+                let mut segments = path.segments.iter();
+                if qself.position > 0 {
+                    out.emit(Kind::Whitespace, " ");
+                    keyword("as", out);
+                    for (i, segment) in segments.by_ref().take(qself.position).enumerate() {
+                        if i > 0 {
+                            out.emit(Kind::Punct, "::");
+                        }
+                        print_segment(segment, false, out);
+                    }
+                }
+                out.emit(Kind::Punct, ">");
+                for segment in segments {
+                    out.emit(Kind::Punct, "::");
+                    print_segment(segment, false, out);
+                }
+            }
+        },
+
+        Type::Ptr(TypePtr { ref mutability, ref elem, .. }) => {
+            out.emit(Kind::Punct, "*");
+            keyword(if mutability.is_some() { "mut" } else { "const" }, out);
+            print_type(elem, out);
+        }
+
+        Type::Reference(TypeReference {
+            ref lifetime,
+            ref mutability,
+            ref elem,
+            ..
+        }) => {
+            out.emit(Kind::Punct, "&");
+            if let Some(ref lifetime) = *lifetime {
+                out.emit(Kind::Lifetime, &lifetime.to_string());
+                out.emit(Kind::Whitespace, " ");
+            }
+            if mutability.is_some() {
+                keyword("mut", out);
+            }
+            print_type(elem, out);
+        }
+
+        Type::Slice(TypeSlice { ref elem, .. }) => {
+            out.emit(Kind::Punct, "[");
+            print_type(elem, out);
+            out.emit(Kind::Punct, "]");
+        }
+
+        Type::TraitObject(TypeTraitObject { ref bounds, .. }) => {
+            keyword("dyn", out);
+            print_bounds(bounds.iter(), out);
+        }
+
+        Type::Tuple(TypeTuple { ref elems, .. }) => {
+            out.emit(Kind::Punct, "(");
+            for (i, elem) in elems.iter().enumerate() {
+                if i > 0 {
+                    comma(out);
+                }
+                print_type(elem, out);
+            }
+            if elems.len() == 1 {
+                out.emit(Kind::Punct, ",");
+            }
+            out.emit(Kind::Punct, ")");
+        }
+
+        ref ty => out.emit(Kind::Path, &ty.to_token_stream().to_string()),
+    }
+}
+
+/// Print the given path, keeping the turbofish `::` of generic arguments in expressions.
+fn print_path(path: &Path, turbofish: bool, out: &mut impl Output) {
+    if path.leading_colon.is_some() {
+        out.emit(Kind::Punct, "::");
+    }
+    for (i, segment) in path.segments.iter().enumerate() {
+        if i > 0 {
+            out.emit(Kind::Punct, "::");
+        }
+        print_segment(segment, turbofish, out);
+    }
+}
+
+fn print_segment(segment: &PathSegment, turbofish: bool, out: &mut impl Output) {
+    let ident = segment.ident.to_string();
+    out.emit(ident_kind(&ident), &ident);
+    match segment.arguments {
+        PathArguments::None => {}
+        PathArguments::AngleBracketed(ref args) => {
+            // rustfmt drops the turbofish `::` of generic arguments in types.
+            if turbofish && args.colon2_token.is_some() {
+                out.emit(Kind::Punct, "::");
+            }
+            out.emit(Kind::Punct, "<");
+            for (i, arg) in args.args.iter().enumerate() {
+                if i > 0 {
+                    comma(out);
+                }
+                print_generic_argument(arg, out);
+            }
+            out.emit(Kind::Punct, ">");
+        }
+        PathArguments::Parenthesized(ref args) => {
+            out.emit(Kind::Punct, "(");
+            for (i, input) in args.inputs.iter().enumerate() {
+                if i > 0 {
+                    comma(out);
+                }
+                print_type(input, out);
+            }
+            out.emit(Kind::Punct, ")");
+            print_return_type(&args.output, out);
+        }
+    }
+}
+
+fn print_generic_argument(arg: &GenericArgument, out: &mut impl Output) {
+    match *arg {
+        GenericArgument::Lifetime(ref lifetime) => {
+            out.emit(Kind::Lifetime, &lifetime.to_string())
+        }
+        GenericArgument::Type(ref ty) => print_type(ty, out),
+        GenericArgument::AssocType(ref assoc) => {
+            out.emit(Kind::Path, &assoc.ident.to_string());
+            out.emit(Kind::Whitespace, " ");
+            out.emit(Kind::Punct, "=");
+            out.emit(Kind::Whitespace, " ");
+            print_type(&assoc.ty, out);
+        }
+        GenericArgument::Const(ref expr) => print_expr(expr, out),
+        GenericArgument::Constraint(ref constraint) => {
+            out.emit(Kind::Path, &constraint.ident.to_string());
+            out.emit(Kind::Punct, ":");
+            out.emit(Kind::Whitespace, " ");
+            print_bounds(constraint.bounds.iter(), out);
+        }
+        ref arg => out.emit(Kind::Literal, &arg.to_token_stream().to_string()),
+    }
+}
+
+fn print_bounds<'a>(bounds: impl Iterator<Item = &'a TypeParamBound>, out: &mut impl Output) {
+    for (i, bound) in bounds.enumerate() {
+        if i > 0 {
+            out.emit(Kind::Whitespace, " ");
+            out.emit(Kind::Punct, "+");
+            out.emit(Kind::Whitespace, " ");
+        }
+        match *bound {
+            TypeParamBound::Trait(ref trt) => {
+                print_bound_lifetimes(&trt.lifetimes, out);
+                if let TraitBoundModifier::Maybe(_) = trt.modifier {
+                    out.emit(Kind::Punct, "?");
+                }
+                print_path(&trt.path, false, out);
+            }
+            TypeParamBound::Lifetime(ref lifetime) => {
+                out.emit(Kind::Lifetime, &lifetime.to_string())
+            }
+            TypeParamBound::PreciseCapture(ref capture) => {
+                out.emit(Kind::Keyword, "use");
+                out.emit(Kind::Punct, "<");
+                for (i, param) in capture.params.iter().enumerate() {
+                    if i > 0 {
+                        comma(out);
+                    }
+                    match *param {
+                        CapturedParam::Lifetime(ref lifetime) => {
+                            out.emit(Kind::Lifetime, &lifetime.to_string())
+                        }
+                        CapturedParam::Ident(ref ident) => out.emit(Kind::Path, &ident.to_string()),
+                        ref param => out.emit(Kind::Path, &param.to_token_stream().to_string()),
+                    }
+                }
+                out.emit(Kind::Punct, ">");
+            }
+            ref bound => out.emit(Kind::Path, &bound.to_token_stream().to_string()),
+        }
+    }
+}
+
+fn print_return_type(output: &ReturnType, out: &mut impl Output) {
+    if let ReturnType::Type(_, ref ty) = *output {
+        out.emit(Kind::Whitespace, " ");
+        out.emit(Kind::Punct, "->");
+        out.emit(Kind::Whitespace, " ");
+        print_type(ty, out);
+    }
+}
+
+fn print_bound_lifetimes(lifetimes: &Option<BoundLifetimes>, out: &mut impl Output) {
+    if let Some(ref lifetimes) = *lifetimes {
+        out.emit(Kind::Keyword, "for");
+        out.emit(Kind::Punct, "<");
+        for (i, param) in lifetimes.lifetimes.iter().enumerate() {
+            if i > 0 {
+                comma(out);
+            }
+            match *param {
+                GenericParam::Lifetime(ref param) => {
+                    out.emit(Kind::Lifetime, &param.lifetime.to_string())
+                }
+                ref param => out.emit(Kind::Path, &param.to_token_stream().to_string()),
+            }
+        }
+        out.emit(Kind::Punct, ">");
+        out.emit(Kind::Whitespace, " ");
+    }
+}
+
+/// Print the given const expression, like an array length or a const generic argument.
+fn print_expr(expr: &Expr, out: &mut impl Output) {
+    match *expr {
+        Expr::Binary(ExprBinary { ref left, ref op, ref right, .. }) => {
+            print_expr(left, out);
+            out.emit(Kind::Whitespace, " ");
+            out.emit(Kind::Punct, &op.to_token_stream().to_string());
+            out.emit(Kind::Whitespace, " ");
+            print_expr(right, out);
+        }
+        Expr::Block(ExprBlock { ref block, .. }) if block.stmts.len() == 1 => {
+            match block.stmts[0] {
+                Stmt::Expr(ref expr, None) => {
+                    out.emit(Kind::Punct, "{");
+                    out.emit(Kind::Whitespace, " ");
+                    print_expr(expr, out);
+                    out.emit(Kind::Whitespace, " ");
+                    out.emit(Kind::Punct, "}");
+                }
+                ref stmt => out.emit(Kind::Literal, &stmt.to_token_stream().to_string()),
+            }
+        }
+        Expr::Lit(ExprLit { lit: Lit::Int(ref lit), .. }) => {
+            out.emit(Kind::Literal, &lit.to_string())
+        }
+        Expr::Lit(ExprLit { ref lit, .. }) => {
+            out.emit(Kind::Literal, &lit.to_token_stream().to_string())
+        }
+        Expr::Paren(ExprParen { ref expr, .. }) => {
+            out.emit(Kind::Punct, "(");
+            print_expr(expr, out);
+            out.emit(Kind::Punct, ")");
+        }
+        Expr::Path(ExprPath { qself: None, ref path, .. }) => print_path(path, true, out),
+        Expr::Unary(ExprUnary { ref op, ref expr, .. }) => {
+            out.emit(Kind::Punct, &op.to_token_stream().to_string());
+            print_expr(expr, out);
+        }
+        ref expr => out.emit(Kind::Literal, &expr.to_token_stream().to_string()),
+    }
+}
+
+/// Print a keyword followed by a space.
+fn keyword(keyword: &str, out: &mut impl Output) {
+    out.emit(Kind::Keyword, keyword);
+    out.emit(Kind::Whitespace, " ");
+}
+
+fn comma(out: &mut impl Output) {
+    out.emit(Kind::Punct, ",");
+    out.emit(Kind::Whitespace, " ");
+}
+
+fn ident_kind(ident: &str) -> Kind {
+    match ident {
+        "bool" | "char" | "str" |
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
+        "f16" | "f32" | "f64" | "f128" => Kind::Primitive,
+        _ => Kind::Path,
+    }
+}
@@ -0,0 +1,143 @@
+//! Truncation of type names.
+//!
+//! Besides cutting every path down to its last segment, lifetimes are normalized the
+//! way `std::any::type_name` erases them. This is a no-op for runtime names.
+
+use syn::*;
+
+/// Truncate every path in the given type to its last segment, optionally keeping the
+/// first segment like `alloc::Vec` when `keep_crate` is set.
+pub fn truncate_type(ty: &mut Type, keep_crate: bool) {
+    match *ty {
+        Type::Infer(_) |
+        Type::Macro(_) |
+        Type::Never(_) |
+        Type::Verbatim(_) => {}
+
+        Type::Array(TypeArray { ref mut elem, .. }) |
+        Type::Group(TypeGroup { group_token: _, ref mut elem }) |
+        Type::Paren(TypeParen { paren_token: _, ref mut elem }) |
+        Type::Ptr(TypePtr { ref mut elem, .. }) |
+        Type::Slice(TypeSlice { ref mut elem, .. }) => truncate_type(elem, keep_crate),
+
+        Type::Reference(TypeReference {
+            ref mut lifetime,
+            ref mut elem,
+            ..
+        }) => {
+            *lifetime = None;
+            truncate_type(elem, keep_crate);
+        }
+
+        Type::Path(ref mut ty) => truncate_path(&mut ty.path, keep_crate),
+
+        Type::BareFn(ref mut ty) => {
+            ty.lifetimes = None;
+            for input in ty.inputs.iter_mut() {
+                truncate_type(&mut input.ty, keep_crate);
+            }
+
+            if let ReturnType::Type(_, ref mut ty) = ty.output {
+                truncate_type(ty.as_mut(), keep_crate);
+            }
+        }
+
+        Type::ImplTrait(ref mut ty) => {
+            for bound in ty.bounds.iter_mut() {
+                if let &mut TypeParamBound::Trait(ref mut trt) = bound {
+                    truncate_path(&mut trt.path, keep_crate);
+                }
+            }
+        }
+
+        Type::TraitObject(ref mut ty) => {
+            ty.bounds = std::mem::take(&mut ty.bounds)
+                .into_iter()
+                .filter(|bound| !matches!(*bound, TypeParamBound::Lifetime(_)))
+                .collect();
+            for bound in ty.bounds.iter_mut() {
+                if let &mut TypeParamBound::Trait(ref mut trt) = bound {
+                    truncate_path(&mut trt.path, keep_crate);
+                }
+            }
+        }
+
+        Type::Tuple(ref mut ty) => {
+            for elem in ty.elems.iter_mut() {
+                truncate_type(elem, keep_crate);
+            }
+        }
+
+        _ => { /* non_exhaustive variants */ }
+    }
+}
+
+/// Truncate the given path to its last segment, optionally keeping the first segment
+/// when `keep_crate` is set.
+fn truncate_path(path: &mut Path, keep_crate: bool) {
+    let path_mut = path;
+    let path = std::mem::replace(
+        path_mut,
+        Path {
+            leading_colon: None,
+            segments: Default::default(),
+        });
+
+    let first_segment = path.segments.first().cloned();
+    let segment_count = path.segments.len();
+    let Some(mut last_segment) = path.segments.into_iter().next_back() else {
+        path_mut.leading_colon = None;
+        path_mut.segments = Default::default();
+        return;
+    };
+
+    match last_segment.arguments {
+        PathArguments::None => {}
+        PathArguments::AngleBracketed(ref mut args) => {
+            args.colon2_token = None;
+            for arg in args.args.iter_mut() {
+                match *arg {
+                    GenericArgument::Lifetime(ref mut lifetime) => {
+                        *lifetime = Lifetime::new("'_", lifetime.span());
+                    }
+                    GenericArgument::Type(ref mut ty) => truncate_type(ty, keep_crate),
+                    GenericArgument::AssocType(ref mut ty) => {
+                        truncate_type(&mut ty.ty, keep_crate)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        PathArguments::Parenthesized(ref mut args) => {
+            for input in args.inputs.iter_mut() {
+                truncate_type(input, keep_crate);
+            }
+            if let ReturnType::Type(_, ref mut output) = args.output {
+                truncate_type(output, keep_crate);
+            }
+        }
+    }
+
+    path_mut.leading_colon = None;
+    path_mut.segments = first_segment
+        .filter(|_| keep_crate && segment_count > 1)
+        .into_iter()
+        .chain(Some(last_segment))
+        .collect();
+}
+
+/// Join a truncated type name and a member name into the canonical member name.
+///
+/// The type is wrapped in angle brackets unless it is a single identifier, so the result
+/// is `Type::member` for plain types and `<Type<T>>::member` for generic or compound
+/// types, regardless of how the type was spelled in the macro call.
+pub fn member_name(type_name: &str, member: &str) -> String {
+    let is_ident = type_name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_');
+    if is_ident {
+        format!("{type_name}::{member}")
+    } else {
+        format!("<{type_name}>::{member}")
+    }
+}
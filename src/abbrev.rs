@@ -0,0 +1,138 @@
+use crate::display;
+
+/// Get the human-friendly type name of given type `T`, abbreviated to at most `max_len`
+/// characters when possible.
+///
+/// Generic arguments are collapsed into `…` level by level, starting from the deepest
+/// one, until the name fits. Every level is collapsed as a whole, so the same type is
+/// always abbreviated the same way for the same limit. Outermost type constructors are
+/// never elided, so the result can still exceed `max_len` if they alone are too long.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_name_abbrev;
+/// type Nested = Option<Result<Vec<Box<u8>>, String>>;
+/// assert_eq!(type_name_abbrev::<Nested>(100), "Option<Result<Vec<Box<u8>>, String>>");
+/// assert_eq!(type_name_abbrev::<Nested>(30), "Option<Result<Vec<…>, String>>");
+/// assert_eq!(type_name_abbrev::<Nested>(20), "Option<Result<…>>");
+/// assert_eq!(type_name_abbrev::<Nested>(0), "Option<…>");
+/// ```
+pub fn type_name_abbrev<T: ?Sized>(max_len: usize) -> String {
+    abbreviate_to_len(&display::<T>().to_string(), max_len)
+}
+
+/// Collapse generic arguments in the given name from the deepest level outward until it
+/// is at most `max_len` characters long or nothing is left to collapse.
+pub(crate) fn abbreviate_to_len(name: &str, max_len: usize) -> String {
+    let mut depth = generic_depth(name);
+    loop {
+        let abbrev = abbreviate(name, depth);
+        if depth == 0 || abbrev.chars().count() <= max_len {
+            return abbrev;
+        }
+        depth -= 1;
+    }
+}
+
+/// Get the human-friendly type name of given type `T`, with generic arguments nested
+/// more than `max_depth` levels deep collapsed into `…`.
+///
+/// A `max_depth` of 0 collapses all generic arguments.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_name_abbrev_depth;
+/// type Nested = Option<Result<Vec<Box<u8>>, String>>;
+/// assert_eq!(type_name_abbrev_depth::<Nested>(2), "Option<Result<Vec<…>, String>>");
+/// assert_eq!(type_name_abbrev_depth::<Nested>(1), "Option<Result<…>>");
+/// assert_eq!(type_name_abbrev_depth::<Nested>(0), "Option<…>");
+/// assert_eq!(type_name_abbrev_depth::<(Vec<u8>, u8)>(0), "(Vec<…>, u8)");
+/// ```
+pub fn type_name_abbrev_depth<T: ?Sized>(max_depth: usize) -> String {
+    abbreviate(&display::<T>().to_string(), max_depth)
+}
+
+/// Replace the contents of every generic argument list nested deeper than `max_depth`
+/// with `…`.
+fn abbreviate(name: &str, max_depth: usize) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut depth = 0;
+    let mut skipping = 0;
+    let mut prev = '\0';
+    for c in name.chars() {
+        let is_open = c == '<';
+        let is_close = c == '>' && prev != '-';
+        prev = c;
+        if skipping > 0 {
+            if is_open {
+                skipping += 1;
+            } else if is_close {
+                skipping -= 1;
+                if skipping == 0 {
+                    out.push('>');
+                }
+            }
+            continue;
+        }
+        if is_open && depth == max_depth {
+            out.push_str("<…");
+            skipping = 1;
+            continue;
+        }
+        if is_open {
+            depth += 1;
+        } else if is_close {
+            depth -= 1;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Get the deepest nesting level of generic argument lists in the given name.
+fn generic_depth(name: &str) -> usize {
+    let mut depth = 0_usize;
+    let mut max_depth = 0;
+    let mut prev = '\0';
+    for c in name.chars() {
+        if c == '<' {
+            depth += 1;
+            max_depth = max_depth.max(depth);
+        } else if c == '>' && prev != '-' {
+            depth = depth.saturating_sub(1);
+        }
+        prev = c;
+    }
+    max_depth
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_abbrev() {
+        type Chain = std::iter::Map<
+            std::iter::Filter<
+                std::iter::Zip<std::vec::IntoIter<u8>, std::slice::Iter<'static, u8>>,
+                fn(&(u8, &u8)) -> bool>,
+            fn((u8, &u8)) -> u8>;
+
+        assert_eq!(
+            type_name_abbrev::<Chain>(usize::MAX),
+            "Map<Filter<Zip<IntoIter<u8>, Iter<'_, u8>>, fn(&(u8, &u8)) -> bool>, fn((u8, &u8)) -> u8>");
+        assert_eq!(
+            type_name_abbrev::<Chain>(84),
+            "Map<Filter<Zip<IntoIter<…>, Iter<…>>, fn(&(u8, &u8)) -> bool>, fn((u8, &u8)) -> u8>");
+        assert_eq!(
+            type_name_abbrev::<Chain>(70),
+            "Map<Filter<Zip<…>, fn(&(u8, &u8)) -> bool>, fn((u8, &u8)) -> u8>");
+        assert_eq!(type_name_abbrev::<Chain>(40), "Map<Filter<…>, fn((u8, &u8)) -> u8>");
+        assert_eq!(type_name_abbrev::<Chain>(0), "Map<…>");
+
+        assert_eq!(type_name_abbrev::<u8>(0), "u8");
+        assert_eq!(type_name_abbrev_depth::<u8>(0), "u8");
+        assert_eq!(type_name_abbrev_depth::<Box<dyn Fn(u8) -> Vec<u8>>>(1), "Box<dyn Fn(u8) -> Vec<…>>");
+        assert_eq!(type_name_abbrev_depth::<Chain>(0), "Map<…>");
+    }
+}
@@ -0,0 +1,46 @@
+//! Compile-time checks of the kind of item a path names, for the naming macros.
+
+/// Implemented by functions and other callables taking up to 12 arguments, with their
+/// signature as `Sig`.
+///
+/// Stable Rust cannot name the signature of a callable without naming its arity, so
+/// callables taking more arguments are not recognized.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a function taking up to 12 arguments",
+    note = "name functions taking more arguments with the `::<..>` placeholder",
+)]
+pub trait FnItem<Sig> {}
+
+/// Implemented by every type with `Sig` being `()`, and by callables like [`FnItem`], so
+/// that `Sig` cannot be inferred for callables.
+pub trait NotFnItem<Sig> {}
+
+impl<T: ?Sized> NotFnItem<()> for T {}
+
+macro_rules! impl_fn_item {
+    ($($arg:ident)*) => {
+        impl<Func: FnOnce($($arg),*) -> Ret, Ret, $($arg),*> FnItem<fn($($arg),*) -> Ret> for Func {}
+        impl<Func: FnOnce($($arg),*) -> Ret, Ret, $($arg),*> NotFnItem<fn($($arg),*) -> Ret> for Func {}
+    };
+}
+
+impl_fn_item!();
+impl_fn_item!(A);
+impl_fn_item!(A B);
+impl_fn_item!(A B C);
+impl_fn_item!(A B C D);
+impl_fn_item!(A B C D E);
+impl_fn_item!(A B C D E F);
+impl_fn_item!(A B C D E F G);
+impl_fn_item!(A B C D E F G H);
+impl_fn_item!(A B C D E F G H I);
+impl_fn_item!(A B C D E F G H I J);
+impl_fn_item!(A B C D E F G H I J K);
+impl_fn_item!(A B C D E F G H I J K L);
+
+/// Check that the given value is a function, failing to compile otherwise.
+pub fn fn_item<T: FnItem<Sig>, Sig>(_: &T) {}
+
+/// Check that the given value is not a function, failing to compile with "type
+/// annotations needed" otherwise.
+pub fn not_fn_item<T: ?Sized + NotFnItem<Sig>, Sig>(_: &T) {}
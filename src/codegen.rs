@@ -0,0 +1,395 @@
+use std::fmt;
+
+use syn::{
+    parse_quote, GenericArgument, Ident, Lifetime, Path, PathArguments, PathSegment, ReturnType, Type,
+    TypeArray, TypeGroup, TypeParamBound, TypeParen, TypePtr, TypeReference, TypeSlice,
+};
+
+use crate::type_name::format_type;
+
+/// Modules of the standard library that re-export all of their public items at the top
+/// level, so that e.g. `core::iter::adapters::map::Map` can be named as `std::iter::Map`.
+const FLAT_MODULES: &[&str] = &[
+    "alloc", "any", "array", "borrow", "boxed", "cell", "char", "cmp", "convert", "env",
+    "error", "ffi", "fmt", "fs", "future", "hash", "io", "iter", "marker", "mem", "net",
+    "num", "ops", "option", "panic", "path", "pin", "process", "ptr", "rc", "result",
+    "slice", "str", "string", "task", "thread", "time", "vec",
+];
+
+/// Public submodules of `std::sync`, which otherwise re-exports its items at the top level.
+const SYNC_MODULES: &[&str] = &["atomic", "mpsc"];
+
+/// Modules of `std::os` that re-export all of their public types at the top level. The
+/// other modules are platforms like `unix`, whose modules like `unix::net` do the same.
+/// `std::os::raw` only has type aliases, which never occur in type names.
+const OS_MODULES: &[&str] = &["fd"];
+
+/// Types re-exported at the top level of `std::collections`.
+const COLLECTIONS: &[&str] = &[
+    "BTreeMap", "BTreeSet", "BinaryHeap", "HashMap", "HashSet", "LinkedList", "TryReserveError",
+    "VecDeque",
+];
+
+/// Error when a type cannot be named in source code, like a closure or an opaque type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnnameableType {
+    /// The full name of the type as given by [`std::any::type_name`].
+    pub type_name: &'static str,
+}
+
+impl fmt::Display for UnnameableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type `{}` cannot be named in source code", self.type_name)
+    }
+}
+
+impl std::error::Error for UnnameableType {}
+
+/// Get the fully-qualified path of given type `T` that can be pasted into generated code.
+///
+/// Every path starts with `::`, and paths into `core`, `alloc` and the private modules of
+/// the standard library are rewritten into their public `std` paths, like
+/// `::std::collections::HashMap` for `std::collections::hash::map::HashMap`.
+///
+/// Lifetimes are erased in [`std::any::type_name`], and are spelled `'static` so that the
+/// path compiles in any position, including type aliases and fields, like
+/// `::std::collections::hash_map::Iter<'static, u8, u8>` or `&'static str`. In the
+/// arguments of function pointers and `Fn` traits they are left elided instead, like
+/// `fn(&str)`, where elision stands for any lifetime.
+///
+/// The private modules are mapped for the modules of the standard library that re-export
+/// their items, including `std::sync`, `std::collections` and the platform modules of
+/// `std::os`, like `::std::os::fd::OwnedFd` for `std::os::fd::owned::OwnedFd`. Other
+/// modules, like the architecture modules of `std::arch`, are kept as they are and may
+/// not compile.
+///
+/// Only paths into the standard library are known to compile. Paths into other crates are
+/// the paths where the items are defined, like `::syn::ty::Type` for `syn::Type`, since
+/// re-exports cannot be seen in [`std::any::type_name`]. They compile only if every
+/// module on the way is public.
+///
+/// Note that the name of a function item looks like a path to a type, so it cannot be
+/// told apart and is not an error.
+///
+/// # Errors
+/// Returns [`UnnameableType`] if `T` is or contains a type that has no name in source
+/// code, like a closure, an async block or an opaque `impl Trait` type.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_path_for_codegen;
+/// assert_eq!(
+///     type_path_for_codegen::<Vec<Option<String>>>().unwrap(),
+///     "::std::vec::Vec<::std::option::Option<::std::string::String>>");
+/// assert_eq!(
+///     type_path_for_codegen::<std::collections::HashMap<u8, &str>>().unwrap(),
+///     "::std::collections::HashMap<u8, &'static str>");
+///
+/// fn path_of_val<T>(_: &T) -> Result<String, pretty_name::UnnameableType> {
+///     type_path_for_codegen::<T>()
+/// }
+/// assert!(path_of_val(&|| ()).is_err());
+/// ```
+pub fn type_path_for_codegen<T: ?Sized>() -> Result<String, UnnameableType> {
+    let type_name = std::any::type_name::<T>();
+    let error = UnnameableType { type_name };
+    let mut ty = syn::parse_str::<Type>(type_name).map_err(|_| error.clone())?;
+    qualify_type(&mut ty, false).ok_or(error)?;
+    Ok(format_type(&ty))
+}
+
+/// Spell an erased lifetime as `'static`, unless it is elided in the arguments of a
+/// function pointer or `Fn` trait, where it stands for any lifetime.
+fn qualify_lifetime(lifetime: &mut Lifetime, elided: bool) {
+    if lifetime.ident == "_" && !elided {
+        *lifetime = parse_quote!('static);
+    }
+}
+
+fn qualify_type(ty: &mut Type, elided: bool) -> Option<()> {
+    match ty {
+        Type::Never(_) => {}
+
+        Type::Reference(TypeReference { lifetime, elem, .. }) => {
+            match lifetime {
+                Some(lifetime) => qualify_lifetime(lifetime, elided),
+                None if !elided => *lifetime = Some(parse_quote!('static)),
+                None => {}
+            }
+            qualify_type(elem, elided)?;
+        }
+
+        Type::Array(TypeArray { elem, .. }) |
+        Type::Group(TypeGroup { elem, .. }) |
+        Type::Paren(TypeParen { elem, .. }) |
+        Type::Ptr(TypePtr { elem, .. }) |
+        Type::Slice(TypeSlice { elem, .. }) => qualify_type(elem, elided)?,
+
+        Type::Path(ty) => {
+            if let Some(qself) = &mut ty.qself {
+                qualify_type(&mut qself.ty, elided)?;
+            }
+            qualify_path(&mut ty.path, elided)?;
+        }
+
+        Type::BareFn(ty) => {
+            for input in ty.inputs.iter_mut() {
+                qualify_type(&mut input.ty, true)?;
+            }
+            if let ReturnType::Type(_, ty) = &mut ty.output {
+                qualify_type(ty, true)?;
+            }
+        }
+
+        Type::TraitObject(ty) => {
+            for bound in ty.bounds.iter_mut() {
+                match bound {
+                    TypeParamBound::Trait(bound) => qualify_path(&mut bound.path, elided)?,
+                    TypeParamBound::Lifetime(lifetime) => qualify_lifetime(lifetime, elided),
+                    _ => {}
+                }
+            }
+        }
+
+        Type::Tuple(ty) => {
+            for elem in ty.elems.iter_mut() {
+                qualify_type(elem, elided)?;
+            }
+        }
+
+        // opaque types, inferred types and anything else that cannot be spelled out
+        _ => return None,
+    }
+    Some(())
+}
+
+fn qualify_path(path: &mut Path, elided: bool) -> Option<()> {
+    for segment in path.segments.iter_mut() {
+        match &mut segment.arguments {
+            PathArguments::None => {}
+            PathArguments::AngleBracketed(args) => {
+                for arg in args.args.iter_mut() {
+                    match arg {
+                        GenericArgument::Lifetime(lifetime) => qualify_lifetime(lifetime, elided),
+                        GenericArgument::Type(ty) => qualify_type(ty, elided)?,
+                        GenericArgument::AssocType(assoc) => qualify_type(&mut assoc.ty, elided)?,
+                        _ => {}
+                    }
+                }
+            }
+            PathArguments::Parenthesized(args) => {
+                for input in args.inputs.iter_mut() {
+                    qualify_type(input, true)?;
+                }
+                if let ReturnType::Type(_, ty) = &mut args.output {
+                    qualify_type(ty, true)?;
+                }
+            }
+        }
+    }
+
+    // single segments are primitive types, since `type_name` always includes the crate
+    if path.segments.len() > 1 {
+        let segments = std::mem::take(&mut path.segments).into_iter().collect::<Vec<_>>();
+        path.segments = public_std_path(segments).into_iter().collect();
+        path.leading_colon = Some(Default::default());
+    }
+    Some(())
+}
+
+/// Rewrite a path into `core`, `alloc` or `std` into the public path of the item in `std`,
+/// keeping other paths as they are, even though they may go through private modules.
+pub(crate) fn public_std_path(mut segments: Vec<PathSegment>) -> Vec<PathSegment> {
+    let ident = |segment: &PathSegment| segment.ident.to_string();
+    if !matches!(ident(&segments[0]).as_str(), "core" | "alloc" | "std") {
+        return segments;
+    }
+    segments[0].ident = Ident::new("std", segments[0].ident.span());
+    if segments.len() <= 3 {
+        return segments;
+    }
+
+    let item = segments.pop().unwrap();
+    let module = ident(&segments[1]);
+    let keep = match module.as_str() {
+        module if FLAT_MODULES.contains(&module) => 2,
+        "sync" if SYNC_MODULES.contains(&ident(&segments[2]).as_str()) => 3,
+        "sync" => 2,
+        "os" if OS_MODULES.contains(&ident(&segments[2]).as_str()) => 3,
+        "os" => 4,
+        "collections" if COLLECTIONS.contains(&ident(&item).as_str()) => 2,
+        "collections" => {
+            // e.g. `hash::map` for `hash_map`
+            let submodule = ident(&segments[2]);
+            if matches!(submodule.as_str(), "hash" | "btree") && segments.len() > 3 {
+                let name = format!("{}_{}", submodule, ident(&segments[3]));
+                segments[2].ident = Ident::new(&name, segments[2].ident.span());
+            }
+            3
+        }
+        _ => segments.len(),
+    };
+    segments.truncate(keep);
+    segments.push(item);
+    segments
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Check that the public path of every type is the path it is named by here, so that
+    /// every entry of the tables maps to a real path in `std`, and get the paths.
+    macro_rules! assert_public_paths {
+        ($($ty:ty,)*) => {{
+            let paths = [$((type_path_for_codegen::<$ty>(), stringify!($ty)),)*];
+            for (path, expected) in &paths {
+                let path = path.as_deref().map(|path| path.replace("::std::", "std::").replace(' ', ""));
+                assert_eq!(path, Ok(expected.replace(' ', "")));
+            }
+            paths.map(|(_, expected)| expected)
+        }};
+    }
+
+    #[test]
+    fn test_public_std_path() {
+        let mut paths = assert_public_paths! {
+            std::alloc::Layout,
+            std::any::TypeId,
+            std::array::IntoIter<u8, 2>,
+            std::borrow::Cow<'static, str>,
+            std::boxed::Box<u8>,
+            std::cell::RefCell<u8>,
+            std::char::ToUppercase,
+            std::cmp::Reverse<u8>,
+            std::convert::Infallible,
+            std::env::Args,
+            std::boxed::Box<dyn std::error::Error>,
+            std::ffi::CString,
+            std::fmt::Arguments<'static>,
+            std::fs::File,
+            std::future::Ready<u8>,
+            std::hash::BuildHasherDefault<std::hash::DefaultHasher>,
+            std::io::BufReader<std::fs::File>,
+            std::iter::Chain<std::option::IntoIter<u8>, std::iter::Empty<u8>>,
+            std::marker::PhantomData<u8>,
+            std::mem::ManuallyDrop<u8>,
+            std::net::TcpStream,
+            std::num::Wrapping<u8>,
+            std::ops::RangeInclusive<u8>,
+            std::option::Option<u8>,
+            std::panic::Location<'static>,
+            std::path::PathBuf,
+            std::pin::Pin<std::boxed::Box<u8>>,
+            std::process::Command,
+            std::ptr::NonNull<u8>,
+            std::rc::Weak<u8>,
+            std::result::Result<u8, u8>,
+            std::slice::Windows<'static, u8>,
+            std::str::Chars<'static>,
+            std::string::Drain<'static>,
+            std::task::Poll<u8>,
+            std::thread::JoinHandle<u8>,
+            std::time::Duration,
+            std::vec::Drain<'static, u8>,
+            std::sync::Mutex<u8>,
+            std::sync::atomic::Ordering,
+            std::sync::mpsc::Receiver<u8>,
+            std::collections::BTreeMap<u8, u8>,
+            std::collections::BTreeSet<u8>,
+            std::collections::BinaryHeap<u8>,
+            std::collections::HashMap<u8, u8>,
+            std::collections::HashSet<u8>,
+            std::collections::LinkedList<u8>,
+            std::collections::TryReserveError,
+            std::collections::VecDeque<u8>,
+            std::collections::binary_heap::Iter<'static, u8>,
+            std::collections::btree_map::Entry<'static, u8, u8>,
+            std::collections::btree_set::Iter<'static, u8>,
+            std::collections::hash_map::Entry<'static, u8, u8>,
+            std::collections::hash_set::Iter<'static, u8>,
+            std::collections::linked_list::Iter<'static, u8>,
+            std::collections::vec_deque::Iter<'static, u8>,
+        }.to_vec();
+        #[cfg(unix)]
+        paths.extend(assert_public_paths! {
+            std::os::fd::OwnedFd,
+            std::os::unix::net::UnixStream,
+            std::os::unix::net::Incoming<'static>,
+        });
+
+        let covered = |prefix: String| paths.iter().any(|path| path.contains(&prefix));
+        for module in FLAT_MODULES {
+            assert!(covered(format!("std::{module}::")), "{module}");
+        }
+        for module in SYNC_MODULES {
+            assert!(covered(format!("std::sync::{module}::")), "{module}");
+        }
+        #[cfg(unix)]
+        for module in OS_MODULES {
+            assert!(covered(format!("std::os::{module}::")), "{module}");
+        }
+        for name in COLLECTIONS {
+            assert!(covered(format!("std::collections::{name}")), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_type_path_for_codegen() {
+        use std::collections::{btree_map, hash_map, BTreeSet, HashMap};
+
+        let paths = [
+            (type_path_for_codegen::<u8>(), "u8"),
+            (type_path_for_codegen::<&mut [u8]>(), "&'static mut [u8]"),
+            (type_path_for_codegen::<(String, [u8; 4])>(), "(::std::string::String, [u8; 4])"),
+            (type_path_for_codegen::<HashMap<u8, u8>>(),
+             "::std::collections::HashMap<u8, u8>"),
+            (type_path_for_codegen::<hash_map::Iter<'static, u8, u8>>(),
+             "::std::collections::hash_map::Iter<'static, u8, u8>"),
+            (type_path_for_codegen::<btree_map::IntoIter<u8, u8>>(),
+             "::std::collections::btree_map::IntoIter<u8, u8>"),
+            (type_path_for_codegen::<BTreeSet<u8>>(), "::std::collections::BTreeSet<u8>"),
+            (type_path_for_codegen::<std::sync::Mutex<u8>>(), "::std::sync::Mutex<u8>"),
+            (type_path_for_codegen::<std::sync::atomic::Ordering>(),
+             "::std::sync::atomic::Ordering"),
+            (type_path_for_codegen::<std::sync::mpsc::Sender<u8>>(),
+             "::std::sync::mpsc::Sender<u8>"),
+            #[cfg(unix)]
+            (type_path_for_codegen::<std::os::fd::OwnedFd>(), "::std::os::fd::OwnedFd"),
+            #[cfg(unix)]
+            (type_path_for_codegen::<std::os::unix::net::UnixStream>(),
+             "::std::os::unix::net::UnixStream"),
+            (type_path_for_codegen::<std::io::BufReader<std::fs::File>>(),
+             "::std::io::BufReader<::std::fs::File>"),
+            (type_path_for_codegen::<std::iter::Map<std::vec::IntoIter<u8>, fn(u8) -> u8>>(),
+             "::std::iter::Map<::std::vec::IntoIter<u8>, fn(u8) -> u8>"),
+            (type_path_for_codegen::<Box<dyn Fn(&str) -> std::rc::Rc<str> + Send>>(),
+             "::std::boxed::Box<dyn ::std::ops::Fn(&'_ str) -> ::std::rc::Rc<str> + ::std::marker::Send>"),
+            (type_path_for_codegen::<Box<dyn Iterator<Item = std::num::NonZeroU8>>>(),
+             "::std::boxed::Box<dyn ::std::iter::Iterator<Item = ::std::num::NonZero<u8>>>"),
+        ];
+        for (path, expected) in paths {
+            assert_eq!(path.as_deref(), Ok(expected));
+        }
+
+        assert_eq!(
+            type_path_for_codegen::<hash_map::Entry<'static, u8, u8>>().as_deref(),
+            Ok("::std::collections::hash_map::Entry<'static, u8, u8>"));
+        assert_eq!(
+            type_path_for_codegen::<(&str, &mut dyn std::any::Any)>().as_deref(),
+            Ok("(&'static str, &'static mut dyn ::std::any::Any)"));
+        assert_eq!(
+            type_path_for_codegen::<fn(std::str::Chars) -> &str>().as_deref(),
+            Ok("fn(::std::str::Chars<'_>) -> &'_ str"));
+
+        fn path_of_val<T>(_: &T) -> Result<String, UnnameableType> {
+            type_path_for_codegen::<T>()
+        }
+        let closure = || 1_u8;
+        let error = path_of_val(&closure).unwrap_err();
+        assert!(error.type_name.ends_with("{{closure}}"));
+        assert!(error.to_string().ends_with("{{closure}}` cannot be named in source code"));
+        assert!(path_of_val(&std::iter::repeat_with(closure)).is_err());
+        assert!(path_of_val(&async {}).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::scan::next_chunk;
+
+/// Maximum length in bytes of a name returned by [`const_type_name`].
+pub const CONST_TYPE_NAME_CAPACITY: usize = 512;
+
+/// Get the human-friendly type name of given type `T` in a `const` context.
+///
+/// This function applies the same truncation as [`type_name`](crate::type_name) with a
+/// const-friendly parser, so the result can be stored in `const` items and associated
+/// consts of generic code without any runtime cost or cache.
+///
+/// Names longer than [`CONST_TYPE_NAME_CAPACITY`] bytes after truncation are rejected at
+/// compile time. Names that [`type_name`](crate::type_name) fails to parse, such as those
+/// of closures, are still truncated here.
+///
+/// Requires the `nightly` feature and a nightly compiler.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::const_type_name;
+/// const NAME: &str = const_type_name::<Vec<Option<String>>>();
+/// assert_eq!(NAME, "Vec<Option<String>>");
+///
+/// struct Wrapper<T>(T);
+/// impl<T> Wrapper<T> {
+///     const NAME: &'static str = const_type_name::<Self>();
+/// }
+/// assert_eq!(Wrapper::<&str>::NAME, "Wrapper<&str>");
+/// ```
+pub const fn const_type_name<T: ?Sized>() -> &'static str {
+    ConstTypeName::<T>::NAME
+}
+
+struct ConstTypeName<T: ?Sized>(core::marker::PhantomData<T>);
+
+impl<T: ?Sized> ConstTypeName<T> {
+    const TRUNCATED: ([u8; CONST_TYPE_NAME_CAPACITY], usize) =
+        truncate(core::any::type_name::<T>().as_bytes());
+
+    const NAME: &'static str = {
+        let (bytes, _) = Self::TRUNCATED.0.split_at(Self::TRUNCATED.1);
+        match core::str::from_utf8(bytes) {
+            Ok(name) => name,
+            Err(_) => panic!("truncated type name is not valid UTF-8"),
+        }
+    };
+}
+
+/// Truncate a type name from `std::any::type_name` into a fixed-size buffer, copying
+/// the chunks to keep byte by byte.
+const fn truncate(name: &[u8]) -> ([u8; CONST_TYPE_NAME_CAPACITY], usize) {
+    let mut out = [0u8; CONST_TYPE_NAME_CAPACITY];
+    let mut len = 0;
+    let mut pos = 0;
+    while pos < name.len() {
+        let (start, end, next) = next_chunk(name, pos);
+        if len + (end - start) > CONST_TYPE_NAME_CAPACITY {
+            panic!("type name is too long for `const_type_name`");
+        }
+        let mut i = start;
+        while i < end {
+            out[len] = name[i];
+            len += 1;
+            i += 1;
+        }
+        pos = next;
+    }
+    (out, len)
+}
+
+#[cfg(test)]
+mod test {
+    use super::const_type_name;
+
+    #[test]
+    fn test_const_type_name() {
+        // names are compared with `type_name` in `test_same_names`
+        struct Wrapper<T>(T);
+        impl<T: ?Sized> Wrapper<Box<T>> {
+            const NAME: &'static str = const_type_name::<Self>();
+        }
+        assert_eq!(Wrapper::<Box<str>>::NAME, "Wrapper<Box<str>>");
+        assert_eq!(Wrapper::<Box<dyn std::fmt::Debug>>::NAME, "Wrapper<Box<dyn Debug>>");
+    }
+}
@@ -0,0 +1,121 @@
+use crate::prettify::{prettify_code, scan_generics};
+use crate::scan::scan_path;
+use crate::truncate::member_name;
+
+/// Demangle a legacy (`_ZN...E`) or v0 (`_R...`) Rust symbol into a human-friendly name,
+/// or get `None` if the symbol is not a Rust symbol.
+///
+/// Every path in the symbol is truncated the same way as [`type_name`](crate::type_name),
+/// hashes are dropped, and the names of methods follow the canonical form of
+/// [`of_method!`](crate::of_method), so that they can be joined against names in logs.
+///
+/// Mangled symbols do not record whether a path segment is a module or a type. For paths
+/// like `my_crate::server::Worker::call`, the last segment starting with an uppercase
+/// letter is taken as the type, following the usual naming conventions.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::demangle;
+/// assert_eq!(demangle("_ZN8my_crate6server6Worker4call17h0123456789abcdefE").unwrap(), "Worker::call");
+/// assert_eq!(demangle("_RNvCs1234_8my_crate4main").unwrap(), "main");
+/// assert_eq!(demangle("not a symbol"), None);
+/// ```
+pub fn demangle(symbol: &str) -> Option<String> {
+    let demangled = rustc_demangle::try_demangle(symbol).ok()?;
+    Some(prettify_symbol(&format!("{demangled:#}")))
+}
+
+/// Prettify a demangled symbol like `<my_crate::Foo<T>>::method::{closure#0}`.
+fn prettify_symbol(symbol: &str) -> String {
+    let bytes = symbol.as_bytes();
+
+    // split the symbol into the owning type, if any, and the rest starting with `::`
+    let (ty, rest) = if bytes.first() == Some(&b'<') {
+        let Some(end) = scan_generics(bytes, 0) else {
+            return prettify_code(symbol).into_owned();
+        };
+        let qualified = prettify_code(&symbol[1..end - 1]).into_owned();
+        if qualified.contains(" as ") {
+            return format!("<{}>{}", qualified, prettify_code(&symbol[end..]));
+        }
+        (qualified, &symbol[end..])
+    } else {
+        let (path_end, _) = scan_path(bytes, 0);
+        let segments = symbol[..path_end].split("::").collect::<Vec<_>>();
+        if let Some(end) = scan_generics(bytes, path_end) {
+            let args = prettify_code(&symbol[path_end..end]);
+            (format!("{}{}", segments[segments.len() - 1], args), &symbol[end..])
+        } else {
+            let ty_index = segments[..segments.len() - 1]
+                .iter()
+                .rposition(|segment| segment.starts_with(|c: char| c.is_ascii_uppercase()));
+            match ty_index {
+                Some(i) => {
+                    let rest_start = segments[..=i].iter().map(|s| s.len() + 2).sum::<usize>();
+                    (segments[i].to_owned(), &symbol[rest_start - 2..])
+                }
+                None => {
+                    let last = segments[segments.len() - 1];
+                    let rest = prettify_code(&symbol[path_end..]);
+                    return format!("{last}{rest}");
+                }
+            }
+        }
+    };
+
+    let Some(member) = rest.strip_prefix("::") else {
+        return format!("{}{}", ty, prettify_code(rest));
+    };
+    let member_end = member
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(member.len());
+    format!(
+        "{}{}",
+        member_name(&ty, &member[..member_end]),
+        prettify_code(&member[member_end..]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_demangle() {
+        // symbols from a crate `m` built with `-C symbol-mangling-version=legacy`
+        let legacy = [
+            ("_ZN1m4main17h4009bc0cd8b193aaE", "main"),
+            ("_ZN1m7generic17hdc9393daa3842e45E", "generic"),
+            ("_ZN1m3Bar5plain17heb8da1ac334597ecE", "Bar::plain"),
+            (
+                "_ZN1m3Bar5plain28_$u7b$$u7b$closure$u7d$$u7d$17h4793537eb43449a0E",
+                "Bar::plain::{{closure}}",
+            ),
+            ("_ZN1m12Foo$LT$T$GT$6method17h2291bcde20114318E", "<Foo<T>>::method"),
+            (
+                "_ZN56_$LT$m..Foo$LT$u32$GT$$u20$as$u20$core..fmt..Display$GT$3fmt17h0e362e9645b6f236E",
+                "<Foo<u32> as Display>::fmt",
+            ),
+        ];
+        // the same symbols built with `-C symbol-mangling-version=v0`
+        let v0 = [
+            ("_RNvCskK7mfDs1mzF_1m4main", "main"),
+            (
+                "_RINvCskK7mfDs1mzF_1m7genericINtNtCslNYArtu3iFV_5alloc3vec3VecNtNtBv_6string6StringEEB2_",
+                "generic::<Vec<String>>",
+            ),
+            ("_RNvMs_CskK7mfDs1mzF_1mNtB4_3Bar5plain", "Bar::plain"),
+            ("_RNCNvMs_CskK7mfDs1mzF_1mNtB6_3Bar5plain0B6_", "Bar::plain::{closure#0}"),
+            ("_RNvMCskK7mfDs1mzF_1mINtB2_3FoomE6methodB2_", "<Foo<u32>>::method"),
+            (
+                "_RNvXs0_CskK7mfDs1mzF_1mINtB5_3FoomENtNtCsgEmfK2I1SDS_4core3fmt7Display3fmt",
+                "<Foo<u32> as Display>::fmt",
+            ),
+        ];
+        for (symbol, name) in legacy.into_iter().chain(v0) {
+            assert_eq!(demangle(symbol).as_deref(), Some(name), "{symbol}");
+        }
+
+        assert_eq!(demangle("main"), None);
+        assert_eq!(demangle("_ZN3foo"), None);
+    }
+}
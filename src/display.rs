@@ -0,0 +1,98 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::scan::next_chunk;
+
+/// Get a zero-sized value that displays the human-friendly type name of given type `T`.
+///
+/// Unlike [`type_name`](crate::type_name), the name is written straight to the
+/// formatter on every use, so nothing is allocated, cached or leaked. This makes it
+/// suitable for long-running processes that see many distinct types. Names that cannot
+/// be parsed as types, such as those of closures, are still truncated here, where
+/// [`type_name`](crate::type_name) gives `<error>`.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::display;
+/// assert_eq!(format!("{}", display::<Option<i32>>()), "Option<i32>");
+/// assert_eq!(format!("{:?}", display::<Vec<std::string::String>>()), "Vec<String>");
+/// ```
+pub fn display<T: ?Sized>() -> PrettyTypeName<T> {
+    PrettyTypeName(PhantomData)
+}
+
+/// Write the human-friendly type name of given type `T` to the given writer without
+/// allocating.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::write_type_name;
+/// let mut log_line = String::from("handler: ");
+/// write_type_name::<Box<dyn std::fmt::Debug>>(&mut log_line).unwrap();
+/// assert_eq!(log_line, "handler: Box<dyn Debug>");
+/// ```
+pub fn write_type_name<T: ?Sized>(out: &mut (impl fmt::Write + ?Sized)) -> fmt::Result {
+    write_truncated(std::any::type_name::<T>(), out)
+}
+
+/// Zero-sized value that displays the human-friendly type name of `T`.
+///
+/// Returned by [`display`].
+pub struct PrettyTypeName<T: ?Sized>(PhantomData<fn() -> *const T>);
+
+impl<T: ?Sized> Clone for PrettyTypeName<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for PrettyTypeName<T> {}
+
+impl<T: ?Sized> Default for PrettyTypeName<T> {
+    fn default() -> Self {
+        display()
+    }
+}
+
+impl<T: ?Sized> fmt::Display for PrettyTypeName<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_type_name::<T>(f)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for PrettyTypeName<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_type_name::<T>(f)
+    }
+}
+
+/// Write a type name from `std::any::type_name` truncated chunk by chunk, with every
+/// chunk borrowed from the input.
+fn write_truncated(name: &str, out: &mut (impl fmt::Write + ?Sized)) -> fmt::Result {
+    let mut pos = 0;
+    while pos < name.len() {
+        let (start, end, next) = next_chunk(name.as_bytes(), pos);
+        out.write_str(&name[start..end])?;
+        pos = next;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        // names are compared with `type_name` in `test_same_names`
+        let name = display::<std::collections::HashMap<u8, std::vec::IntoIter<&'static str>>>();
+        assert_eq!(format!("{name}"), "HashMap<u8, IntoIter<&str>>");
+        assert_eq!(format!("{name:?}"), "HashMap<u8, IntoIter<&str>>");
+        assert_eq!(PrettyTypeName::<u8>::default().to_string(), "u8");
+
+        let mut out = String::from("<");
+        write_type_name::<dyn std::io::Write + Send>(&mut out).unwrap();
+        write_type_name::<&mut std::string::String>(&mut out).unwrap();
+        assert_eq!(out, "<dyn Write + Send&mut String");
+    }
+}
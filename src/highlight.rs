@@ -0,0 +1,179 @@
+use syn::Type;
+
+use crate::print::{Kind, Output, print_type};
+use crate::truncate::truncate_type;
+use crate::type_name;
+
+/// Get the human-friendly type name of given type `T` with ANSI color codes for terminal
+/// output.
+///
+/// Paths, primitive types, lifetimes, keywords like `dyn` and `impl`, literals and
+/// punctuation are colored differently. The text is the same as [`type_name`] apart from
+/// the escape codes. If the `NO_COLOR` environment variable is set to a non-empty value,
+/// the name is returned without any color.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_name_ansi;
+/// let name = type_name_ansi::<Box<dyn Send>>();
+/// if std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) {
+///     assert_eq!(name, "\x1b[36mBox\x1b[0m\x1b[2m<\x1b[0m\x1b[1;34mdyn\x1b[0m \x1b[36mSend\x1b[0m\x1b[2m>\x1b[0m");
+/// }
+/// ```
+pub fn type_name_ansi<T: ?Sized>() -> String {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if no_color {
+        type_name::<T>().to_owned()
+    } else {
+        to_ansi(&tokens::<T>())
+    }
+}
+
+/// Get the human-friendly type name of given type `T` as HTML, with every token wrapped
+/// in a `<span>` with a CSS class.
+///
+/// The classes are `pn-path`, `pn-primitive`, `pn-lifetime`, `pn-keyword`, `pn-literal`
+/// and `pn-punct`. Whitespace is left unwrapped and special characters are escaped, so
+/// the text content is the same as [`type_name`].
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_name_html;
+/// assert_eq!(
+///     type_name_html::<&[u8]>(),
+///     r#"<span class="pn-punct">&amp;[</span><span class="pn-primitive">u8</span><span class="pn-punct">]</span>"#);
+/// ```
+pub fn type_name_html<T: ?Sized>() -> String {
+    to_html(&tokens::<T>())
+}
+
+/// Printed tokens of a type name, with adjacent punctuation merged into one token.
+#[derive(Default)]
+struct Tokens(Vec<(Kind, String)>);
+
+impl Output for Tokens {
+    fn emit(&mut self, kind: Kind, text: &str) {
+        match self.0.last_mut() {
+            Some((Kind::Punct, last)) if kind == Kind::Punct => last.push_str(text),
+            _ => self.0.push((kind, text.to_owned())),
+        }
+    }
+}
+
+/// Get the tokens of the name of `T`, printed from the same truncated type as
+/// [`type_name`] so that the text cannot differ.
+fn tokens<T: ?Sized>() -> Tokens {
+    let mut tokens = Tokens::default();
+    match syn::parse_str::<Type>(std::any::type_name::<T>()) {
+        Ok(mut ty) => {
+            truncate_type(&mut ty, false);
+            print_type(&ty, &mut tokens);
+        }
+        Err(_) => tokens.emit(Kind::Path, type_name::<T>()),
+    }
+    tokens
+}
+
+fn to_ansi(tokens: &Tokens) -> String {
+    let mut out = String::new();
+    for (kind, token) in &tokens.0 {
+        let style = match kind {
+            Kind::Path => "36",
+            Kind::Primitive => "33",
+            Kind::Lifetime => "35",
+            Kind::Keyword => "1;34",
+            Kind::Literal => "32",
+            Kind::Punct => "2",
+            Kind::Whitespace => {
+                out.push_str(token);
+                continue;
+            }
+        };
+        out.push_str("\x1b[");
+        out.push_str(style);
+        out.push('m');
+        out.push_str(token);
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+fn to_html(tokens: &Tokens) -> String {
+    let mut out = String::new();
+    for (kind, token) in &tokens.0 {
+        let class = match kind {
+            Kind::Path => "pn-path",
+            Kind::Primitive => "pn-primitive",
+            Kind::Lifetime => "pn-lifetime",
+            Kind::Keyword => "pn-keyword",
+            Kind::Literal => "pn-literal",
+            Kind::Punct => "pn-punct",
+            Kind::Whitespace => {
+                out.push_str(token);
+                continue;
+            }
+        };
+        out.push_str("<span class=\"");
+        out.push_str(class);
+        out.push_str("\">");
+        for c in token.chars() {
+            match c {
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '&' => out.push_str("&amp;"),
+                '"' => out.push_str("&quot;"),
+                c => out.push(c),
+            }
+        }
+        out.push_str("</span>");
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut rest = s;
+        while let Some(start) = rest.find('\x1b') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start + rest[start..].find('m').unwrap() + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    #[test]
+    fn test_highlight() {
+        macro_rules! assert_same_text {
+            ($($ty:ty),* $(,)?) => {$(
+                assert_eq!(strip_ansi(&to_ansi(&tokens::<$ty>())), type_name::<$ty>());
+            )*};
+        }
+
+        assert_same_text!(
+            i32, &'static str, &mut [u8; 4], *const (), (i32, bool),
+            Option<Vec<String>>, std::borrow::Cow<'static, str>,
+            unsafe extern "C" fn(i32) -> u8, Box<dyn Fn(&str) -> String + Send>,
+        );
+
+        assert_eq!(
+            to_ansi(&tokens::<std::borrow::Cow<'static, str>>()),
+            "\x1b[36mCow\x1b[0m\x1b[2m<\x1b[0m\x1b[35m'_\x1b[0m\x1b[2m,\x1b[0m \
+             \x1b[33mstr\x1b[0m\x1b[2m>\x1b[0m");
+        assert_eq!(
+            to_html(&tokens::<extern "C" fn() -> [u8; 4]>()),
+            "<span class=\"pn-keyword\">extern</span> \
+             <span class=\"pn-literal\">&quot;C&quot;</span> \
+             <span class=\"pn-keyword\">fn</span>\
+             <span class=\"pn-punct\">()</span> \
+             <span class=\"pn-punct\">-&gt;</span> \
+             <span class=\"pn-punct\">[</span>\
+             <span class=\"pn-primitive\">u8</span>\
+             <span class=\"pn-punct\">;</span> \
+             <span class=\"pn-literal\">4</span>\
+             <span class=\"pn-punct\">]</span>");
+    }
+}
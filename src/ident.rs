@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::*;
+
+/// Style of the identifiers produced by [`type_ident_with_style`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum IdentStyle {
+    /// Words capitalized and concatenated, like `OptionVecUser`.
+    #[default]
+    UpperCamel,
+    /// Words joined by underscores as they are, like `Option_Vec_User`.
+    Underscore,
+    /// Words lowercased and joined by underscores, like `option_vec_user`.
+    Snake,
+}
+
+/// Get an identifier for given type `T`, like `OptionVecUser` for `Option<Vec<User>>`.
+///
+/// Same as [`type_ident_with_style`] with [`IdentStyle::UpperCamel`].
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_ident;
+/// assert_eq!(type_ident::<Option<Vec<String>>>(), "OptionVecString");
+/// assert_eq!(type_ident::<&[u8; 4]>(), "RefArray4U8");
+/// ```
+pub fn type_ident<T: ?Sized>() -> String {
+    type_ident_with_style::<T>(IdentStyle::UpperCamel)
+}
+
+/// Get an identifier for given type `T` in the given style, for names of schema
+/// components and the like.
+///
+/// The identifier is derived deterministically from the words of the human-friendly type
+/// name that [`type_name`] produces. Lifetimes are dropped, and the other type forms are
+/// spelled out with marker words:
+///
+/// | Type                 | Words                          |
+/// |----------------------|--------------------------------|
+/// | `&T`, `&mut T`       | `Ref T`, `RefMut T`            |
+/// | `*const T`, `*mut T` | `PtrConst T`, `PtrMut T`       |
+/// | `[T; N]`, `[T]`      | `Array N T`, `Slice T`         |
+/// | `()`, `(A, B)`       | `Unit`, `Tuple A And B`        |
+/// | `fn(A, B) -> C`      | `FnPtr A And B Returns C`      |
+/// | `dyn A + B`          | `Dyn A And B`                  |
+/// | `impl A + B`         | `Impl A And B`                 |
+/// | `Foo<A, B>`          | `Foo A And B`                  |
+/// | `Foo<Item = A>`      | `Foo Item Is A`                |
+/// | `!`, `_`             | `Never`, `Infer`               |
+///
+/// A type with arguments that is followed by more words of its parent, like `Vec<u8>` in
+/// `Result<Vec<u8>, Error>`, is closed with an `End` word, so that no two types in a type
+/// tree that differ only in nesting get the same identifier.
+///
+/// Different types in one type tree that would still be written the same way, like
+/// `FooBar` and `Foo<Bar>` in `(FooBar, Foo<Bar>)`, or `a::User` and `b::User` in
+/// `(a::User, b::User)`, are told apart by a number after the later one, giving
+/// `TupleFooBarAndFooBar_2` and `TupleUserAndUser_2`. Types are compared by their full
+/// paths. The number is preceded by `_` in [`IdentStyle::UpperCamel`], where words never
+/// contain underscores, and by `__` in the other styles, and is increased further if the
+/// result would still be written like another type in the tree. Closures are named after
+/// the function they are defined in, like `MainClosure` for a closure in `main`.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::{type_ident_with_style, IdentStyle};
+/// type Response = Result<Vec<u8>, std::io::Error>;
+/// let ident = type_ident_with_style::<Response>;
+/// assert_eq!(ident(IdentStyle::UpperCamel), "ResultVecU8EndAndError");
+/// assert_eq!(ident(IdentStyle::Underscore), "Result_Vec_u8_End_And_Error");
+/// assert_eq!(ident(IdentStyle::Snake), "result_vec_u8_end_and_error");
+/// ```
+pub fn type_ident_with_style<T: ?Sized>(style: IdentStyle) -> String {
+    let words = type_words::<T>();
+    match style {
+        IdentStyle::UpperCamel => words.render(camelize, "", "_"),
+        IdentStyle::Underscore => words.render(str::to_owned, "_", "__"),
+        IdentStyle::Snake => words.render(str::to_lowercase, "_", "__"),
+    }
+}
+
+/// Get a slug for given type `T` to use in file names, like `option-vec-user` for
+/// `Option<Vec<User>>`.
+///
+/// The slug has the same words as [`type_ident_with_style`], lowercased and joined by
+/// dashes, with underscores in identifiers replaced by dashes as well.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_slug;
+/// assert_eq!(type_slug::<Option<Vec<String>>>(), "option-vec-string");
+/// assert_eq!(type_slug::<(u8, &str)>(), "tuple-u8-and-ref-str");
+/// ```
+pub fn type_slug<T: ?Sized>() -> String {
+    type_words::<T>().render(|word| word.to_lowercase().replace('_', "-"), "-", "--")
+}
+
+/// Words of a type, with the span of words of every type in it.
+#[derive(Default)]
+struct Words {
+    words: Vec<String>,
+    /// Start and end of the words of each type, and the type with its full paths.
+    types: Vec<(usize, usize, String)>,
+}
+
+impl Words {
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    fn push(&mut self, word: String) {
+        self.words.push(word);
+    }
+
+    /// Render every word with `render` and join them with `separator`, numbering types
+    /// that would otherwise be rendered like a different type with `marker` and a number
+    /// after their last word.
+    ///
+    /// Types are visited inner first, so the number of an inner type also tells apart
+    /// the types it is part of.
+    fn render(&self, render: impl Fn(&str) -> String, separator: &str, marker: &str) -> String {
+        let mut words = self.words.iter().map(|word| render(word)).collect::<Vec<_>>();
+        let mut seen = HashMap::<String, &str>::new();
+        for &(start, end, ref ty) in &self.types {
+            let plain = words[start..end].join(separator);
+            let mut rendered = plain.clone();
+            let mut index = 1;
+            while seen.get(&rendered).is_some_and(|&other| other != ty) {
+                index += 1;
+                rendered = format!("{plain}{marker}{index}");
+            }
+            if index > 1 {
+                words[end - 1].push_str(&format!("{marker}{index}"));
+            }
+            seen.insert(rendered, ty);
+        }
+        words.join(separator)
+    }
+}
+
+impl Extend<String> for Words {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        self.words.extend(iter);
+    }
+}
+
+fn type_words<T: ?Sized>() -> Words {
+    // closures and the like are named after the function they are defined in, like
+    // `main::{{closure}}`, which is turned into the identifier `main_closure`
+    let name = std::any::type_name::<T>().replace("::{{", "_").replace("}}", "");
+    let mut words = Words::default();
+    match syn::parse_str::<Type>(&name) {
+        // paths are cut down to their last segment while pushing words, so that types
+        // are still told apart by their full paths
+        Ok(ty) => push_type(&ty, true, &mut words),
+        Err(_) => push_text(&name, &mut words),
+    }
+    words
+}
+
+/// Push the words of the given type.
+fn push_type(ty: &Type, tail: bool, words: &mut Words) {
+    let start = words.len();
+    match ty {
+        Type::Array(ty) => {
+            words.push("Array".to_owned());
+            push_expr(&ty.len, words);
+            push_type(&ty.elem, tail, words);
+        }
+        Type::BareFn(ty) => {
+            if ty.unsafety.is_some() {
+                words.push("Unsafe".to_owned());
+            }
+            if let Some(abi) = &ty.abi {
+                words.push("Extern".to_owned());
+                if let Some(name) = &abi.name {
+                    push_text(&name.value(), words);
+                }
+            }
+            words.push("FnPtr".to_owned());
+            let inputs = ty.inputs.iter().map(|input| &input.ty);
+            push_fn_signature(inputs, &ty.output, tail, words);
+        }
+        Type::Group(ty) => push_type(&ty.elem, tail, words),
+        Type::ImplTrait(ty) => {
+            words.push("Impl".to_owned());
+            push_bounds(&ty.bounds, tail, words);
+        }
+        Type::Infer(_) => words.push("Infer".to_owned()),
+        Type::Never(_) => words.push("Never".to_owned()),
+        Type::Paren(ty) => push_type(&ty.elem, tail, words),
+        Type::Path(ty) => {
+            if let Some(qself) = &ty.qself {
+                push_type(&qself.ty, false, words);
+                words.push("As".to_owned());
+            }
+            push_path(&ty.path, tail, words);
+        }
+        Type::Ptr(ty) => {
+            words.push(if ty.mutability.is_some() { "PtrMut" } else { "PtrConst" }.to_owned());
+            push_type(&ty.elem, tail, words);
+        }
+        Type::Reference(ty) => {
+            words.push(if ty.mutability.is_some() { "RefMut" } else { "Ref" }.to_owned());
+            push_type(&ty.elem, tail, words);
+        }
+        Type::Slice(ty) => {
+            words.push("Slice".to_owned());
+            push_type(&ty.elem, tail, words);
+        }
+        Type::TraitObject(ty) => {
+            words.push("Dyn".to_owned());
+            push_bounds(&ty.bounds, tail, words);
+        }
+        Type::Tuple(ty) if ty.elems.is_empty() => words.push("Unit".to_owned()),
+        Type::Tuple(ty) => {
+            words.push("Tuple".to_owned());
+            push_list(ty.elems.iter(), tail, words, push_type);
+        }
+        ty => push_text(&ty.to_token_stream().to_string(), words),
+    }
+    let end = words.len();
+    words.types.push((start, end, ty.to_token_stream().to_string()));
+    close(start, tail, words);
+}
+
+/// Close the words pushed since `start` with `End` if there is more than one of them and
+/// they are not at the `tail` of the identifier.
+fn close(start: usize, tail: bool, words: &mut Words) {
+    if !tail && words.len() - start > 1 {
+        words.push("End".to_owned());
+    }
+}
+
+/// Push the words of the last segment of the given path, the same way as
+/// [`type_name`](crate::type_name) truncates it.
+fn push_path(path: &Path, tail: bool, words: &mut Words) {
+    if let Some(segment) = path.segments.last() {
+        words.push(segment.ident.unraw().to_string());
+        match &segment.arguments {
+            PathArguments::None => {}
+            PathArguments::AngleBracketed(args) => {
+                let args = args
+                    .args
+                    .iter()
+                    .filter(|arg| !matches!(arg, GenericArgument::Lifetime(_)));
+                push_list(args, tail, words, push_generic_argument);
+            }
+            PathArguments::Parenthesized(args) => {
+                push_fn_signature(args.inputs.iter(), &args.output, tail, words);
+            }
+        }
+    }
+}
+
+fn push_generic_argument(arg: &GenericArgument, tail: bool, words: &mut Words) {
+    match arg {
+        GenericArgument::Type(ty) => push_type(ty, tail, words),
+        GenericArgument::Const(expr) => push_expr(expr, words),
+        GenericArgument::AssocType(assoc) => {
+            words.push(assoc.ident.unraw().to_string());
+            words.push("Is".to_owned());
+            push_type(&assoc.ty, tail, words);
+        }
+        GenericArgument::AssocConst(assoc) => {
+            words.push(assoc.ident.unraw().to_string());
+            words.push("Is".to_owned());
+            push_expr(&assoc.value, words);
+        }
+        arg => push_text(&arg.to_token_stream().to_string(), words),
+    }
+}
+
+fn push_fn_signature<'a>(
+    inputs: impl Iterator<Item = &'a Type>,
+    output: &ReturnType,
+    tail: bool,
+    words: &mut Words,
+) {
+    let has_output = matches!(output, ReturnType::Type(..));
+    push_list(inputs, tail && !has_output, words, push_type);
+    if let ReturnType::Type(_, ty) = output {
+        words.push("Returns".to_owned());
+        push_type(ty, tail, words);
+    }
+}
+
+fn push_bounds<P>(
+    bounds: &punctuated::Punctuated<TypeParamBound, P>,
+    tail: bool,
+    words: &mut Words,
+) {
+    let traits = bounds.iter().filter_map(|bound| match bound {
+        TypeParamBound::Trait(bound) => Some(&bound.path),
+        _ => None,
+    });
+    push_list(traits, tail, words, |path, tail, words| {
+        let start = words.len();
+        push_path(path, tail, words);
+        close(start, tail, words);
+    });
+}
+
+/// Push the words of the given items joined by `And`, where only the last item can be at
+/// the tail.
+fn push_list<I: IntoIterator>(
+    items: I,
+    tail: bool,
+    words: &mut Words,
+    mut push_item: impl FnMut(I::Item, bool, &mut Words),
+) {
+    let items = items.into_iter().collect::<Vec<_>>();
+    let last = items.len().saturating_sub(1);
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            words.push("And".to_owned());
+        }
+        push_item(item, tail && i == last, words);
+    }
+}
+
+/// Push a const expression like an array length as a single word, like `4` or `Neg1`.
+fn push_expr(expr: &Expr, words: &mut Words) {
+    let text = expr.to_token_stream().to_string();
+    let mut word = String::new();
+    if text.starts_with('-') {
+        word.push_str("Neg");
+    }
+    word.extend(text.chars().filter(|&c| c.is_alphanumeric() || c == '_'));
+    words.push(if word.is_empty() { "Const".to_owned() } else { word });
+}
+
+/// Push the identifier-like runs of characters in the given text as words.
+fn push_text(text: &str, words: &mut Words) {
+    words.extend(
+        text.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|word| !word.is_empty())
+            .map(str::to_owned));
+}
+
+/// Capitalize every part of the given word between underscores, like `MyType` for
+/// `my_type`.
+fn camelize(word: &str) -> String {
+    word.split('_')
+        .flat_map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().into_iter().flat_map(char::to_uppercase);
+            first.chain(chars)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{type_ident, type_ident_with_style, type_slug, IdentStyle};
+
+    #[test]
+    fn test_type_ident() {
+        trait Handler<T> {}
+        struct User;
+
+        let idents = [
+            (type_ident::<User>(), "User"),
+            (type_ident::<Option<Vec<User>>>(), "OptionVecUser"),
+            (type_ident::<std::collections::HashMap<String, Vec<u8>>>(), "HashMapStringAndVecU8"),
+            (type_ident::<Result<Vec<u8>, String>>(), "ResultVecU8EndAndString"),
+            (type_ident::<Result<Option<Vec<u8>>, String>>(), "ResultOptionVecU8EndEndAndString"),
+            (type_ident::<&mut &'static str>(), "RefMutRefStr"),
+            (type_ident::<*const [u8]>(), "PtrConstSliceU8"),
+            (type_ident::<[[u8; 2]; 16]>(), "Array16Array2U8"),
+            (type_ident::<()>(), "Unit"),
+            (type_ident::<(u8,)>(), "TupleU8"),
+            (type_ident::<((u8, u16), u32)>(), "TupleTupleU8AndU16EndAndU32"),
+            (type_ident::<(u8, (u16, u32))>(), "TupleU8AndTupleU16AndU32"),
+            (type_ident::<fn()>(), "FnPtr"),
+            (type_ident::<fn(u8, &str) -> bool>(), "FnPtrU8AndRefStrEndReturnsBool"),
+            (type_ident::<unsafe extern "C" fn()>(), "UnsafeExternCFnPtr"),
+            (type_ident::<Box<dyn Fn(u8) -> u8 + Send>>(), "BoxDynFnU8ReturnsU8EndAndSend"),
+            (type_ident::<Box<dyn Handler<u8> + Send>>(), "BoxDynHandlerU8EndAndSend"),
+            (type_ident::<Box<dyn Iterator<Item = u8>>>(), "BoxDynIteratorItemIsU8"),
+            (type_ident::<dyn std::any::Any + Send>(), "DynAnyAndSend"),
+            (type_ident::<fn() -> !>(), "FnPtrReturnsNever"),
+        ];
+        for (ident, expected) in idents {
+            assert_eq!(ident, expected);
+        }
+
+        type Nested = Option<(&'static str, [u8; 4])>;
+        assert_eq!(
+            type_ident_with_style::<Nested>(IdentStyle::UpperCamel),
+            "OptionTupleRefStrEndAndArray4U8");
+        assert_eq!(
+            type_ident_with_style::<Nested>(IdentStyle::Underscore),
+            "Option_Tuple_Ref_str_End_And_Array_4_u8");
+        assert_eq!(
+            type_ident_with_style::<Nested>(IdentStyle::Snake),
+            "option_tuple_ref_str_end_and_array_4_u8");
+        assert_eq!(type_slug::<Nested>(), "option-tuple-ref-str-end-and-array-4-u8");
+
+        let closure = || ();
+        fn ident_of_val<T>(_: &T) -> String {
+            type_ident::<T>()
+        }
+        assert_eq!(ident_of_val(&closure), "TestTypeIdentClosure");
+        assert_eq!(ident_of_val(&Some(closure)), "OptionTestTypeIdentClosure");
+
+        #[allow(non_camel_case_types)]
+        struct Foo_Bar;
+        struct FooBar;
+        struct Foo<T>(T);
+        struct Bar;
+        assert_eq!(type_ident::<(FooBar, Foo<Bar>)>(), "TupleFooBarAndFooBar_2");
+        assert_eq!(type_ident::<(Foo<Bar>, FooBar, Foo<Bar>)>(), "TupleFooBarEndAndFooBar_2AndFooBar");
+        assert_eq!(
+            type_ident_with_style::<(FooBar, Foo<Bar>)>(IdentStyle::Underscore),
+            "Tuple_FooBar_And_Foo_Bar");
+        assert_eq!(
+            type_ident_with_style::<(Foo_Bar, Foo<Bar>)>(IdentStyle::Underscore),
+            "Tuple_Foo_Bar_And_Foo_Bar__2");
+
+        // numbered types are not written like types that happen to end in a number
+        #[allow(non_camel_case_types)]
+        struct Foo_Bar__2;
+        struct FooBar2;
+        assert_eq!(type_ident::<(FooBar, FooBar2, Foo<Bar>)>(), "TupleFooBarAndFooBar2AndFooBar_2");
+        assert_eq!(
+            type_ident_with_style::<(Foo_Bar, Foo_Bar__2, Foo<Bar>)>(IdentStyle::Underscore),
+            "Tuple_Foo_Bar_And_Foo_Bar__2_And_Foo_Bar__3");
+
+        // types are told apart by their full paths
+        mod a {
+            pub struct User;
+        }
+        mod b {
+            pub struct User;
+        }
+        assert_eq!(type_ident::<(a::User, b::User)>(), "TupleUserAndUser_2");
+        assert_eq!(type_ident::<(a::User, a::User)>(), "TupleUserAndUser");
+        assert_eq!(type_ident::<(Vec<a::User>, Vec<b::User>)>(), "TupleVecUserEndAndVecUser_2");
+        assert_eq!(type_slug::<(a::User, b::User)>(), "tuple-user-and-user--2");
+    }
+}
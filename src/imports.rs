@@ -0,0 +1,313 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use syn::{GenericArgument, Path, PathArguments, ReturnType, Type, TypeParamBound};
+
+use crate::codegen::public_std_path;
+use crate::type_name;
+
+/// Items of the standard prelude, which are in scope without an import.
+const PRELUDE: &[&str] = &[
+    "std::boxed::Box",
+    "std::clone::Clone",
+    "std::cmp::Eq",
+    "std::cmp::Ord",
+    "std::cmp::PartialEq",
+    "std::cmp::PartialOrd",
+    "std::convert::AsMut",
+    "std::convert::AsRef",
+    "std::convert::From",
+    "std::convert::Into",
+    "std::convert::TryFrom",
+    "std::convert::TryInto",
+    "std::default::Default",
+    "std::future::Future",
+    "std::future::IntoFuture",
+    "std::iter::DoubleEndedIterator",
+    "std::iter::ExactSizeIterator",
+    "std::iter::Extend",
+    "std::iter::FromIterator",
+    "std::iter::IntoIterator",
+    "std::iter::Iterator",
+    "std::marker::Copy",
+    "std::marker::Send",
+    "std::marker::Sized",
+    "std::marker::Sync",
+    "std::marker::Unpin",
+    "std::ops::AsyncFn",
+    "std::ops::AsyncFnMut",
+    "std::ops::AsyncFnOnce",
+    "std::ops::Drop",
+    "std::ops::Fn",
+    "std::ops::FnMut",
+    "std::ops::FnOnce",
+    "std::option::Option",
+    "std::result::Result",
+    "std::string::String",
+    "std::string::ToString",
+    "std::vec::Vec",
+];
+
+/// Error when two paths in a type would be written under the same name, so that the
+/// human-friendly type name is ambiguous.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportCollision {
+    /// The name shared by both paths.
+    pub name: &'static str,
+    /// The path that comes first in sorted order, which may be an item of the prelude.
+    pub first: &'static str,
+    /// The other path.
+    pub second: &'static str,
+}
+
+impl fmt::Display for ImportCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` and `{}` would both be named `{}`", self.first, self.second, self.name)
+    }
+}
+
+impl std::error::Error for ImportCollision {}
+
+struct Imports {
+    paths: &'static [&'static str],
+    collision: Option<ImportCollision>,
+}
+
+/// Get the human-friendly type name of given type `T` together with the `use` paths that
+/// make it resolve, like `std::collections::HashMap` or `my_crate::model::User`.
+///
+/// The paths are deduplicated and sorted. Paths into `core`, `alloc` and private modules
+/// of the standard library are given as their public `std` paths, and items of the
+/// prelude like `Vec` and `Option` are left out. Other paths are given as
+/// [`std::any::type_name`] spells them, starting with the crate name. Those may go through
+/// private modules or function bodies, in which case they cannot be imported as is and
+/// have to be replaced by a public path, or `crate::` within the defining crate.
+///
+/// Two paths may end in the same name, like `std::io::Error` and `std::fmt::Error`, in
+/// which case importing both does not work. Use [`try_type_name_with_imports`] to detect
+/// that.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_name_with_imports;
+/// type Cache = std::collections::HashMap<String, Result<Vec<u8>, std::io::Error>>;
+/// let (name, imports) = type_name_with_imports::<Cache>();
+/// assert_eq!(name, "HashMap<String, Result<Vec<u8>, Error>>");
+/// assert_eq!(imports, ["std::collections::HashMap", "std::io::Error"]);
+/// ```
+pub fn type_name_with_imports<T: ?Sized>() -> (&'static str, &'static [&'static str]) {
+    (type_name::<T>(), imports::<T>().paths)
+}
+
+/// Same as [`type_name_with_imports`], but fails if two types in `T` would be written under
+/// the same name, including items of the prelude and of other crates.
+///
+/// # Errors
+/// Returns [`ImportCollision`] with the first two paths sharing a name.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::try_type_name_with_imports;
+/// type Errors = (std::io::Error, std::fmt::Error);
+/// let collision = try_type_name_with_imports::<Errors>().unwrap_err();
+/// assert_eq!(
+///     collision.to_string(),
+///     "`std::fmt::Error` and `std::io::Error` would both be named `Error`");
+/// ```
+pub fn try_type_name_with_imports<T: ?Sized>()
+    -> Result<(&'static str, &'static [&'static str]), ImportCollision>
+{
+    let imports = imports::<T>();
+    match &imports.collision {
+        Some(collision) => Err(collision.clone()),
+        None => Ok((type_name::<T>(), imports.paths)),
+    }
+}
+
+fn imports<T: ?Sized>() -> &'static Imports {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    thread_local! {
+        static IMPORTS_CACHE: RefCell<HashMap<&'static str, &'static Imports>> =
+            RefCell::new(HashMap::new());
+    }
+
+    let full_name = std::any::type_name::<T>();
+    IMPORTS_CACHE.with_borrow_mut(|cache| {
+        *cache.entry(full_name).or_insert_with(|| Box::leak(Box::new(find_imports(full_name))))
+    })
+}
+
+fn find_imports(full_name: &str) -> Imports {
+    let mut paths = BTreeSet::new();
+    if let Ok(ty) = syn::parse_str::<Type>(full_name) {
+        collect_type(&ty, &mut paths);
+    }
+
+    let mut collision = None;
+    let mut names = BTreeSet::new();
+    for path in &paths {
+        let name = &path[path.rfind("::").map_or(0, |i| i + 2)..];
+        if !names.insert(name) && collision.is_none() {
+            let first = paths.iter().find(|other| other.ends_with(&format!("::{name}"))).unwrap();
+            collision = Some(ImportCollision {
+                name: leak(name),
+                first: leak(first),
+                second: leak(path),
+            });
+        }
+    }
+
+    let paths = paths
+        .iter()
+        .filter(|path| !PRELUDE.contains(&path.as_str()))
+        .map(|path| leak(path))
+        .collect::<Vec<_>>();
+    Imports { paths: Box::leak(paths.into_boxed_slice()), collision }
+}
+
+fn leak(text: &str) -> &'static str {
+    Box::leak(text.to_owned().into_boxed_str())
+}
+
+fn collect_type(ty: &Type, paths: &mut BTreeSet<String>) {
+    match ty {
+        Type::Array(ty) => collect_type(&ty.elem, paths),
+        Type::Group(ty) => collect_type(&ty.elem, paths),
+        Type::Paren(ty) => collect_type(&ty.elem, paths),
+        Type::Ptr(ty) => collect_type(&ty.elem, paths),
+        Type::Reference(ty) => collect_type(&ty.elem, paths),
+        Type::Slice(ty) => collect_type(&ty.elem, paths),
+
+        Type::Path(ty) => match &ty.qself {
+            // qualified paths like `<T as Trait>::Assoc` are not shortened
+            Some(qself) => collect_type(&qself.ty, paths),
+            None => collect_path(&ty.path, paths),
+        },
+
+        Type::BareFn(ty) => {
+            for input in &ty.inputs {
+                collect_type(&input.ty, paths);
+            }
+            if let ReturnType::Type(_, ty) = &ty.output {
+                collect_type(ty, paths);
+            }
+        }
+
+        Type::ImplTrait(ty) => collect_bounds(ty.bounds.iter(), paths),
+        Type::TraitObject(ty) => collect_bounds(ty.bounds.iter(), paths),
+
+        Type::Tuple(ty) => {
+            for elem in &ty.elems {
+                collect_type(elem, paths);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+fn collect_bounds<'a>(
+    bounds: impl Iterator<Item = &'a TypeParamBound>,
+    paths: &mut BTreeSet<String>,
+) {
+    for bound in bounds {
+        if let TypeParamBound::Trait(bound) = bound {
+            collect_path(&bound.path, paths);
+        }
+    }
+}
+
+fn collect_path(path: &Path, paths: &mut BTreeSet<String>) {
+    let Some(last) = path.segments.last() else {
+        return;
+    };
+    match &last.arguments {
+        PathArguments::None => {}
+        PathArguments::AngleBracketed(args) => {
+            for arg in &args.args {
+                match arg {
+                    GenericArgument::Type(ty) => collect_type(ty, paths),
+                    GenericArgument::AssocType(assoc) => collect_type(&assoc.ty, paths),
+                    _ => {}
+                }
+            }
+        }
+        PathArguments::Parenthesized(args) => {
+            for input in &args.inputs {
+                collect_type(input, paths);
+            }
+            if let ReturnType::Type(_, ty) = &args.output {
+                collect_type(ty, paths);
+            }
+        }
+    }
+
+    // single segments are primitive types, since `type_name` always includes the crate
+    if path.segments.len() > 1 {
+        let segments = public_std_path(path.segments.iter().cloned().collect());
+        let path = segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        paths.insert(path);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_type_name_with_imports() {
+        mod fmt {
+            pub struct Debug;
+        }
+        mod prelude {
+            pub struct Option;
+        }
+
+        assert_eq!(type_name_with_imports::<u8>(), ("u8", &[][..]));
+        assert_eq!(type_name_with_imports::<Option<Vec<String>>>(), ("Option<Vec<String>>", &[][..]));
+        assert_eq!(
+            type_name_with_imports::<Box<dyn std::fmt::Debug + Send>>(),
+            ("Box<dyn Debug + Send>", &["std::fmt::Debug"][..]));
+        assert_eq!(
+            type_name_with_imports::<(std::sync::Arc<std::sync::Mutex<u8>>, std::rc::Rc<u8>)>(),
+            ("(Arc<Mutex<u8>>, Rc<u8>)", &["std::rc::Rc", "std::sync::Arc", "std::sync::Mutex"][..]));
+        assert_eq!(
+            type_name_with_imports::<fn(&std::path::Path) -> std::io::Result<std::fs::File>>(),
+            ("fn(&Path) -> Result<File, Error>",
+             &["std::fs::File", "std::io::Error", "std::path::Path"][..]));
+        assert_eq!(
+            type_name_with_imports::<std::collections::hash_map::Entry<'static, u8, u8>>(),
+            ("Entry<'_, u8, u8>", &["std::collections::hash_map::Entry"][..]));
+        assert!(try_type_name_with_imports::<std::collections::BTreeMap<u8, std::io::Error>>().is_ok());
+
+        // items outside of the standard library, here inside a function body
+        assert_eq!(
+            type_name_with_imports::<(fmt::Debug, std::rc::Rc<u8>)>(),
+            ("(Debug, Rc<u8>)",
+             &["pretty_name::imports::test::test_type_name_with_imports::fmt::Debug", "std::rc::Rc"][..]));
+        assert_eq!(
+            type_name_with_imports::<Vec<crate::IdentStyle>>(),
+            ("Vec<IdentStyle>", &["pretty_name::ident::IdentStyle"][..]));
+
+        let collision = try_type_name_with_imports::<(fmt::Debug, Box<dyn std::fmt::Debug>)>()
+            .unwrap_err();
+        assert_eq!(collision.name, "Debug");
+        assert_eq!(
+            collision.first,
+            "pretty_name::imports::test::test_type_name_with_imports::fmt::Debug");
+        assert_eq!(collision.second, "std::fmt::Debug");
+
+        // shadowing an item of the prelude
+        let collision = try_type_name_with_imports::<Option<prelude::Option>>().unwrap_err();
+        assert_eq!(
+            collision.first,
+            "pretty_name::imports::test::test_type_name_with_imports::prelude::Option");
+        assert_eq!(collision.second, "std::option::Option");
+    }
+}
@@ -1,9 +1,79 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "nightly", feature(const_type_name))]
+
+use pretty_name_syntax::{print, truncate};
+
+mod scan;
 
 mod type_name;
 pub use type_name::type_name;
 pub use type_name::type_name_of_val;
 
+mod display;
+pub use display::{display, write_type_name, PrettyTypeName};
+
+mod named;
+pub use named::{AnyNamed, Named};
+
+mod named_any;
+pub use named_any::{DowncastError, NamedAny, NamedAnySend, NamedAnySendSync};
+
+mod ident;
+pub use ident::{type_ident, type_ident_with_style, type_slug, IdentStyle};
+
+mod codegen;
+pub use codegen::{type_path_for_codegen, UnnameableType};
+
+mod imports;
+pub use imports::{try_type_name_with_imports, type_name_with_imports, ImportCollision};
+
+mod abbrev;
+pub use abbrev::{type_name_abbrev, type_name_abbrev_depth};
+
+mod demangle;
+pub use demangle::demangle;
+
+pub mod panic;
+
+pub mod registry;
+/// Derive macro that registers the type in [`registry`] automatically.
+#[cfg(feature = "derive")]
+pub use pretty_name_macros::PrettyName;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use inventory as __inventory;
+
+mod prettify;
+pub use prettify::{prettify_text, prettify_text_with, PrettifyOptions};
+
+mod tree;
+pub use tree::{type_name_pretty_tree, type_name_pretty_tree_with_width, DEFAULT_MAX_WIDTH};
+
+#[cfg(feature = "highlight")]
+mod highlight;
+#[cfg(feature = "highlight")]
+pub use highlight::{type_name_ansi, type_name_html};
+
+mod check;
+
+#[cfg(feature = "nightly")]
+mod const_type_name;
+#[cfg(feature = "nightly")]
+pub use const_type_name::{const_type_name, CONST_TYPE_NAME_CAPACITY};
+
+#[doc(hidden)]
+pub use type_name::truncate_type_name as __truncate_type_name;
+#[doc(hidden)]
+pub use truncate::member_name as __member_name;
+#[doc(hidden)]
+pub use pretty_name_macros::lexical_type_name as __lexical_type_name;
+#[doc(hidden)]
+pub use pretty_name_macros::lexical_member_name as __lexical_member_name;
+#[doc(hidden)]
+pub use pretty_name_macros::check_trait as __check_trait;
+#[doc(hidden)]
+pub use check::{fn_item as __fn_item, not_fn_item as __not_fn_item};
+
 /// Internal helper macro for caching string results in thread-local storage.
 ///
 /// This macro wraps an expression that produces a `String` and caches it as a
@@ -22,17 +92,112 @@ macro_rules! __with_cache {
     }};
 }
 
+/// Internal helper macro for caching string results that depend on types.
+///
+/// Like [`__with_cache!`], but the cache entry is keyed on the name of the given type, so
+/// that a single macro invocation inside a generic function or a trait default method
+/// caches one result per type it is instantiated with. Mention every type the result
+/// depends on in the key, like `(PhantomData<Self>, PhantomData<T>)`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_type_cache {
+    ($key:ty, $expr:expr) => {{
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+        thread_local! {
+            static CACHE: RefCell<HashMap<&'static str, &'static str>> =
+                RefCell::new(HashMap::new());
+        }
+        CACHE.with_borrow_mut(|cache| *cache
+            .entry(::core::any::type_name::<$key>())
+            .or_insert_with(|| {
+                let result = $expr;
+                Box::leak(result.into_boxed_str())
+            }))
+    }};
+}
+
+/// Internal helper macro for getting the name of a type.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __type_name_of {
+    ($ty:ty) => {
+        $crate::type_name::<$ty>()
+    };
+}
+
+/// Internal helper macro for getting the canonical name of a member like `Type::member`,
+/// optionally followed by generic arguments like `Type::member::<T>`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __member_name_of {
+    ($ty:ty, $member:tt) => {
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<$ty>,
+            $crate::__member_name($crate::type_name::<$ty>(), stringify!($member)))
+    };
+    ($ty:ty, $member:tt $(, $arg:ty)+) => {
+        $crate::__with_type_cache!(
+            (::core::marker::PhantomData<$ty>, $(::core::marker::PhantomData<$arg>),+),
+            format!(
+                "{}::<{}>",
+                $crate::__member_name($crate::type_name::<$ty>(), stringify!($member)),
+                vec![$($crate::type_name::<$arg>()),*].join(", ")))
+    };
+}
+
+/// Internal helper macro for the `const` form of the naming macros, which names the
+/// given member path from its tokens, or falls back to the runtime form of the naming
+/// macro.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __lexical_member_of {
+    ($macro:ident, $($path:tt)+) => {
+        $crate::__lexical_member_name!($($path)+; $crate::$macro!($($path)+))
+    };
+}
+
+/// Internal helper macro for getting the last segment of a path as a string literal.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __last_segment {
+    ($last:ident) => {
+        stringify!($last)
+    };
+    ($first:ident $($rest:ident)+) => {
+        $crate::__last_segment!($($rest)+)
+    };
+}
+
 /// Get the name of the given local variable or constant as a string literal.
 /// 
 /// This macro checks that the identifier is valid in the current scope. If the identifier
 /// is renamed via refactoring tools, the macro call will be updated accordingly.
+///
+/// Constants and statics can also be given by path like `module::CONSTANT`, in which
+/// case only the last segment is returned. Use [`of_path!`] to get the full path. Paths
+/// are checked not to name functions, which fail to compile with "type annotations
+/// needed", so constants of function pointer types can only be named by identifier.
+/// Like in [`of_function!`], only functions taking up to 12 arguments are recognized.
 /// 
 /// # Examples
 /// ```rust
+/// mod my_module {
+///     pub const MY_CONSTANT: u32 = 42;
+/// }
 /// let my_variable = 42;
 /// const MY_CONSTANT: u32 = 42;
 /// assert_eq!(pretty_name::of_var!(my_variable), "my_variable");
 /// assert_eq!(pretty_name::of_var!(MY_CONSTANT), "MY_CONSTANT");
+/// assert_eq!(pretty_name::of_var!(my_module::MY_CONSTANT), "MY_CONSTANT");
+/// ```
+///
+/// Functions are rejected at compile time:
+/// ```compile_fail
+/// mod my_module {
+///     pub fn my_function() {}
+/// }
+/// let _ = pretty_name::of_var!(my_module::my_function);
 /// ```
 #[macro_export]
 macro_rules! of_var {
@@ -40,23 +205,58 @@ macro_rules! of_var {
         let _ = &$ident;
         stringify!($ident)
     }};
+    ($first:ident $(:: $seg:ident)+) => {{
+        $crate::__not_fn_item(&$first $(:: $seg)+);
+        $crate::__last_segment!($first $($seg)+)
+    }};
 }
 
 /// Get the name of the given function as a `&'static str`.
 ///
 /// Use a `::<..>` placeholder to exclude generic parameters in the output, see examples.
+///
+/// Functions can also be given by path like `module::function`, in which case only the
+/// last segment is returned. Use [`of_path!`] to get the full path. Paths are checked to
+/// name functions, or other callables like tuple struct constructors, except with the
+/// `::<..>` placeholder, where the path is only checked to be importable. This check
+/// only recognizes functions taking up to 12 arguments; name functions taking more
+/// arguments with the placeholder.
 /// 
 /// # Examples
 /// ```rust
+/// mod my_module {
+///     pub fn my_function() {}
+///     pub fn my_generic_function<T>() {}
+/// }
 /// fn my_function() {}
 /// fn my_generic_function<T>() {}
 /// fn my_generic_function_2args<T, U>() {}
+/// assert_eq!(pretty_name::of_function!(my_module::my_function), "my_function");
+/// assert_eq!(pretty_name::of_function!(my_module::my_generic_function::<..>), "my_generic_function");
+/// assert_eq!(pretty_name::of_function!(my_module::my_generic_function::<u32>), "my_generic_function::<u32>");
 /// assert_eq!(pretty_name::of_function!(my_function), "my_function");
 /// assert_eq!(pretty_name::of_function!(my_generic_function::<..>), "my_generic_function");
 /// assert_eq!(pretty_name::of_function!(my_generic_function::<u32>), "my_generic_function::<u32>");
 /// assert_eq!(pretty_name::of_function!(my_generic_function_2args::<..>), "my_generic_function_2args");
 /// assert_eq!(pretty_name::of_function!(my_generic_function_2args::<u32, String>), "my_generic_function_2args::<u32, String>");
 /// ```
+///
+/// Structs, modules and constants are rejected at compile time:
+/// ```compile_fail
+/// mod my_module {
+///     pub struct MyStruct;
+/// }
+/// let _ = pretty_name::of_function!(my_module::MyStruct);
+/// ```
+/// ```compile_fail
+/// mod my_module {
+///     pub const MY_CONSTANT: u32 = 42;
+/// }
+/// let _ = pretty_name::of_function!(my_module::MY_CONSTANT);
+/// ```
+/// ```compile_fail
+/// let _ = pretty_name::of_function!(std::collections);
+/// ```
 #[macro_export]
 macro_rules! of_function {
     // IMPLEMENTATION NOTE:
@@ -76,39 +276,343 @@ macro_rules! of_function {
     }};
     ($ident:ident ::<$($arg:ty),*>) => {{
         let _ = &$ident::<$($arg),*>;
-        $crate::__with_cache!(
+        $crate::__with_type_cache!(
+            ($(::core::marker::PhantomData<$arg>,)*),
             format!(
                 "{}::<{}>",
                 stringify!($ident),
                 vec![$($crate::type_name::<$arg>()),*].join(", ")))
     }};
+
+    ($first:ident $(:: $seg:ident)+) => {{
+        #[allow(unused)] use $first $(:: $seg)+;
+        $crate::__fn_item(&$first $(:: $seg)+);
+        $crate::__last_segment!($first $($seg)+)
+    }};
+    ($first:ident $(:: $seg:ident)+ ::<..>) => {{
+        #[allow(unused)] use $first $(:: $seg)+;
+        $crate::__last_segment!($first $($seg)+)
+    }};
+    ($first:ident $(:: $seg:ident)+ ::<$($arg:ty),*>) => {{
+        $crate::__fn_item(&$first $(:: $seg)+ ::<$($arg),*>);
+        $crate::__with_type_cache!(
+            ($(::core::marker::PhantomData<$arg>,)*),
+            format!(
+                "{}::<{}>",
+                $crate::__last_segment!($first $($seg)+),
+                vec![$($crate::type_name::<$arg>()),*].join(", ")))
+    }};
+}
+
+/// Get the name of the given module as a string literal.
+///
+/// This macro checks that the path names a module (or an enum) by glob-importing it.
+/// Nested modules can be given by path like `module::inner`, in which case only the last
+/// segment is returned. Use [`of_path!`] to get the full path.
+///
+/// # Examples
+/// ```rust
+/// mod my_module {
+///     pub mod inner {}
+/// }
+/// assert_eq!(pretty_name::of_mod!(my_module), "my_module");
+/// assert_eq!(pretty_name::of_mod!(my_module::inner), "inner");
+/// assert_eq!(pretty_name::of_mod!(std::collections), "collections");
+/// ```
+#[macro_export]
+macro_rules! of_mod {
+    ($first:ident $(:: $seg:ident)*) => {{
+        #[allow(unused)] use $first $(:: $seg)* ::*;
+        $crate::__last_segment!($first $($seg)*)
+    }};
+}
+
+/// Get the name of the given static item as a string literal.
+///
+/// This macro checks that the path names a static by importing it and taking its raw
+/// address, which rejects local variables, constants and functions. No value is read, so
+/// `static mut` items are accepted without `unsafe`. Statics can be given by path like
+/// `module::STATIC`, in which case only the last segment is returned. Use [`of_path!`] to
+/// get the full path.
+///
+/// # Examples
+/// ```rust
+/// mod my_module {
+///     pub static MY_STATIC: u32 = 42;
+/// }
+/// static MY_STATIC: u32 = 42;
+/// static mut MY_MUT_STATIC: u32 = 42;
+/// assert_eq!(pretty_name::of_static!(MY_STATIC), "MY_STATIC");
+/// assert_eq!(pretty_name::of_static!(MY_MUT_STATIC), "MY_MUT_STATIC");
+/// assert_eq!(pretty_name::of_static!(my_module::MY_STATIC), "MY_STATIC");
+/// ```
+///
+/// Constants are rejected at compile time:
+/// ```compile_fail
+/// const MY_CONSTANT: u32 = 42;
+/// let _ = pretty_name::of_static!(MY_CONSTANT);
+/// ```
+#[macro_export]
+macro_rules! of_static {
+    ($first:ident $(:: $seg:ident)*) => {{
+        #[allow(unused)] use $first $(:: $seg)*;
+        let _ = &raw const $first $(:: $seg)*;
+        $crate::__last_segment!($first $($seg)*)
+    }};
+}
+
+/// Get the name of the given macro as a string literal, without the trailing `!`.
+///
+/// This macro imports the path, which works for both `macro_rules!` macros in scope and
+/// macros referred to by path, so that renames are picked up. An import does not tell
+/// apart macros from other items of the same name, and a path cannot be checked to name
+/// a macro without invoking it, so `of_macro!(my_function)` compiles as well.
+///
+/// Give an invocation like `of_macro!(my_macro!(..))` to check that the path names a
+/// macro that accepts the given input. The invocation is expanded inside a closure that
+/// is never called, which also counts as a use of a `macro_rules!` macro, so that it
+/// does not trigger the `unused_macros` lint. Macros can be given by path like
+/// `module::my_macro`, in which case only the last segment is returned. Use
+/// [`of_path!`] to get the full path.
+///
+/// # Examples
+/// ```rust
+/// macro_rules! my_macro {
+///     ($name:ident) => {};
+/// }
+/// assert_eq!(pretty_name::of_macro!(my_macro!(x)), "my_macro");
+/// assert_eq!(pretty_name::of_macro!(my_macro), "my_macro");
+/// assert_eq!(pretty_name::of_macro!(std::println), "println");
+/// assert_eq!(pretty_name::of_macro!(std::println!()), "println");
+/// ```
+///
+/// Other items are only rejected with an invocation:
+/// ```compile_fail
+/// fn my_function() {}
+/// let _ = pretty_name::of_macro!(my_function!());
+/// ```
+#[macro_export]
+macro_rules! of_macro {
+    ($first:ident $(:: $seg:ident)* ! $args:tt) => {{
+        #[allow(unused)] use $first $(:: $seg)*;
+        #[allow(unused)]
+        let _ = || { $first $(:: $seg)* ! $args; };
+        $crate::__last_segment!($first $($seg)*)
+    }};
+    ($first:ident $(:: $seg:ident)*) => {{
+        #[allow(unused)] use $first $(:: $seg)*;
+        $crate::__last_segment!($first $($seg)*)
+    }};
+}
+
+/// Get the full path of the given item as a string literal, exactly as written.
+///
+/// This macro checks that the path names an importable item, such as a module,
+/// function, type, trait, constant, static or macro, by importing it.
+///
+/// # Examples
+/// ```rust
+/// mod my_module {
+///     pub fn my_function() {}
+/// }
+/// assert_eq!(pretty_name::of_path!(my_module::my_function), "my_module::my_function");
+/// assert_eq!(pretty_name::of_path!(std::collections::HashMap), "std::collections::HashMap");
+/// ```
+#[macro_export]
+macro_rules! of_path {
+    ($first:ident $(:: $seg:ident)*) => {{
+        #[allow(unused)] use $first $(:: $seg)*;
+        concat!(stringify!($first) $(, "::", stringify!($seg))*)
+    }};
 }
 
 /// Get the name of the given type as a `&'static str`.
 /// 
 /// This macro resolves `Self` to the appropriate type when used inside an `impl` block.
 /// 
-/// If the given type is a single identifier and is not `Self`, the macro expands to a
-/// string literal at compile time. For more complex types, the macro uses runtime type
-/// name retrieval with caching.
+/// If the given type is a single identifier and is not `Self`, the macro checks that it
+/// names an item in the type namespace and expands to a string literal at compile time.
+/// This works for generic types named without their arguments, like `of_type!(Vec)`, and
+/// rejects functions, constants, statics and macros. Modules and traits share the type
+/// namespace and are accepted as well: telling them apart from types needs a type
+/// position, which in turn needs the arguments of generic types. Generic parameters are
+/// rejected, since their name is not the name of a type; use [`type_name`] to get the name
+/// of the type they stand for. For more complex types, the macro uses runtime type name
+/// retrieval with caching.
+///
+/// Prefix the type with `const` like `of_type!(const Vec<u8>)` to get a string literal
+/// for complex types as well, truncated from the tokens of the type with the same rules
+/// as [`type_name`], so that it can be used in `const` contexts.
+///
+/// A type alias or a renamed import cannot be told apart from a type by its tokens, and
+/// its name is not the name of the type, like `"Result<(), Error>"` for
+/// `io::Result<()>`. So the `const` form falls back to the runtime form, which cannot be
+/// used in `const` contexts, for every type whose name cannot be proven from its tokens: types mentioning `Self`, qualified paths
+/// like `<T as Trait>::Assoc`, `impl Trait`, arrays with non-literal lengths, and paths
+/// into `std`, `core` and `alloc` other than the known locations of common standard
+/// library types and traits. Common standard library items named by a single
+/// identifier, like `Vec` or `dyn Debug`, are checked at compile time to be the standard
+/// library items, so a local `type Result<T> = ..` fails to compile. Other paths are
+/// taken to name your own types; aliases and renamed imports of your own types cannot be
+/// detected, and are named as written. Generic parameters are rejected at compile time,
+/// since their names are not known from the tokens either.
 /// 
 /// # Examples
 /// ```rust
 /// struct MyStruct;
 /// struct MyGenericStruct<T>(std::marker::PhantomData<T>);
 /// assert_eq!(pretty_name::of_type!(MyStruct), "MyStruct");
+/// assert_eq!(pretty_name::of_type!(MyGenericStruct), "MyGenericStruct");
 /// assert_eq!(pretty_name::of_type!(MyGenericStruct<u32>), "MyGenericStruct<u32>");
+/// assert_eq!(pretty_name::of_type!(Vec), "Vec");
+///
+/// const NAME: &str = pretty_name::of_type!(const std::collections::HashMap<u8, Vec<String>>);
+/// assert_eq!(NAME, "HashMap<u8, Vec<String>>");
+/// const MY_NAME: &str = pretty_name::of_type!(const Option<MyGenericStruct<u8>>);
+/// assert_eq!(MY_NAME, "Option<MyGenericStruct<u8>>");
+///
+/// // `NonZeroU32` is an alias of `NonZero<u32>`, named at runtime
+/// assert_eq!(pretty_name::of_type!(const std::num::NonZeroU32), "NonZero<u32>");
+/// ```
+///
+/// Misspelled or removed types are rejected at compile time:
+/// ```compile_fail
+/// struct MyStruct;
+/// let _ = pretty_name::of_type!(MyStrcut);
+/// ```
+///
+/// So are functions, constants, statics and macros:
+/// ```compile_fail
+/// fn my_function() {}
+/// let _ = pretty_name::of_type!(my_function);
+/// ```
+/// ```compile_fail
+/// const MY_CONST: u32 = 0;
+/// let _ = pretty_name::of_type!(MY_CONST);
+/// ```
+/// ```compile_fail
+/// static MY_STATIC: u32 = 0;
+/// let _ = pretty_name::of_type!(MY_STATIC);
+/// ```
+/// ```compile_fail
+/// let _ = pretty_name::of_type!(println);
+/// ```
+///
+/// Generic parameters have no name at compile time:
+/// ```compile_fail
+/// fn generic<T>() -> &'static str {
+///     pretty_name::of_type!(T)
+/// }
+/// ```
+/// ```compile_fail
+/// fn generic<T>() -> &'static str {
+///     pretty_name::of_type!(const Vec<T>)
+/// }
+/// ```
+///
+/// Type aliases named like standard library types are rejected as well:
+/// ```compile_fail
+/// type Result<T> = std::result::Result<T, std::io::Error>;
+/// const NAME: &str = pretty_name::of_type!(const Result<()>);
 /// ```
 #[macro_export]
 macro_rules! of_type {
+    // IMPLEMENTATION NOTE:
+    //   - A type position or an expression path like `$ty::ITEM` would only accept
+    //     types, but both need the arguments of generic types, so the $ty:ident arm
+    //     checks the type namespace with imports instead, which keeps auto-completion.
+    //   - The identifier is first imported as `__PrettyNameType`, in a nested item where
+    //     the generic parameters of the surrounding function are not in scope. Two glob
+    //     imports then define the identifier in the type namespace, ambiguously, and the
+    //     innermost block imports `__PrettyNameType` back under its name, which shadows
+    //     them in the namespaces the item is defined in. So the final import only
+    //     resolves without ambiguity if the item is in the type namespace.
+    (const $ty:ty) => {
+        $crate::__lexical_type_name!($ty; $crate::of_type!($ty))
+    };
     (Self) => {{
         $crate::type_name::<Self>()
     }};
     ($ty:ident) => {{
+        const _: () = {
+            #[allow(unused_imports)]
+            use $ty as __PrettyNameType;
+            const _: () = {
+                #[allow(dead_code, non_camel_case_types)]
+                mod __pretty_name_a { pub struct $ty {} }
+                #[allow(dead_code, non_camel_case_types)]
+                mod __pretty_name_b { pub struct $ty {} }
+                #[allow(unused_imports)]
+                use __pretty_name_a::*;
+                #[allow(unused_imports)]
+                use __pretty_name_b::*;
+                const _: () = {
+                    #[allow(unused_imports)]
+                    use __PrettyNameType as $ty;
+                    #[allow(unused_imports)]
+                    use $ty as _;
+                };
+            };
+        };
         stringify!($ty)
     }};
     ($ty:ty) => {{
-        $crate::type_name::<$ty>()
+        $crate::__type_name_of!($ty)
+    }};
+}
+
+/// Get the name of the given trait as a `&'static str`.
+///
+/// This macro checks that the given path names a trait by using it as a generic bound,
+/// so traits that are not dyn-compatible are accepted as well. The generic arguments of
+/// the trait may use the generic parameters of the surrounding code. The `dyn Trait` form
+/// additionally requires the trait to be dyn-compatible, and accepts further bounds like
+/// `dyn Trait + Send + 'static`.
+///
+/// If the given trait is a single identifier, the macro expands to a string literal at
+/// compile time. For more complex forms, such as generic traits, qualified paths wrapped
+/// in angle brackets, or `dyn` trait objects, the name is truncated the same way as
+/// [`type_name`] does, and cached at runtime.
+///
+/// # Examples
+/// ```rust
+/// mod my_module {
+///     pub trait MyTrait {}
+/// }
+/// trait MyGenericTrait<T> {}
+/// use my_module::MyTrait;
+/// assert_eq!(pretty_name::of_trait!(MyTrait), "MyTrait");
+/// assert_eq!(pretty_name::of_trait!(Clone), "Clone");
+/// assert_eq!(pretty_name::of_trait!(MyGenericTrait<u32>), "MyGenericTrait<u32>");
+/// assert_eq!(pretty_name::of_trait!(MyGenericTrait<std::string::String>), "MyGenericTrait<String>");
+/// assert_eq!(pretty_name::of_trait!(<my_module::MyTrait>), "MyTrait");
+/// assert_eq!(pretty_name::of_trait!(dyn Iterator<Item = u8>), "dyn Iterator<Item = u8>");
+/// assert_eq!(pretty_name::of_trait!(dyn std::error::Error + Send + Sync), "dyn Error + Send + Sync");
+/// ```
+///
+/// Types are rejected at compile time:
+/// ```compile_fail
+/// struct MyStruct;
+/// let _ = pretty_name::of_trait!(MyStruct);
+/// ```
+#[macro_export]
+macro_rules! of_trait {
+    (dyn $($bounds:tt)+) => {{
+        $crate::type_name::<dyn $($bounds)+>()
+    }};
+    ($trait:ident) => {{
+        #[allow(unused)] fn check<T: ?Sized + $trait>() {}
+        stringify!($trait)
+    }};
+    (<$trait:path>) => {{
+        $crate::__check_trait!($trait);
+        $crate::__with_cache!(
+            $crate::__truncate_type_name(stringify!($trait)))
+    }};
+    ($trait:path) => {{
+        $crate::__check_trait!($trait);
+        $crate::__with_cache!(
+            $crate::__truncate_type_name(stringify!($trait)))
     }};
 }
 
@@ -116,13 +620,19 @@ macro_rules! of_type {
 ///
 /// This macro resolves `Self` to the appropriate type when used inside an `impl` block.
 ///
-/// By default, this macro expects a simple type identifier like `Type::field`. To use
-/// types with qualified path or generic parameters, wrap the type in angle brackets
-/// like `<Type<T>>::field` or `<module::Type>::field`.
+/// Fields of tuple structs are named by index like `Type::0`. Union fields are supported
+/// as well, and are validated without reading the union.
+///
+/// By default, this macro expects a simple type identifier like `Type::field`. Generic
+/// types can be written in turbofish style like `Type::<T>::field`. To use types with
+/// qualified path, wrap the type in angle brackets like `<module::Type>::field`.
 /// 
 /// If the *Type* part is a single identifier and is not `Self`, the macro expands to a
 /// string literal at compile time. For more complex types, the macro uses runtime type
 /// name retrieval with caching.
+///
+/// Prefix the path with `const` to get a string literal for complex types as well, with
+/// the same fallback to the runtime form as the `const` form of [`of_type!`].
 /// 
 /// # Examples
 /// ```rust
@@ -133,23 +643,54 @@ macro_rules! of_type {
 ///     my_field: T,
 /// }
 /// assert_eq!(pretty_name::of_field!(MyStruct::my_field), "MyStruct::my_field");
+/// assert_eq!(pretty_name::of_field!(MyGenericStruct::<u32>::my_field), "<MyGenericStruct<u32>>::my_field");
 /// assert_eq!(pretty_name::of_field!(<MyGenericStruct<u32>>::my_field), "<MyGenericStruct<u32>>::my_field");
+///
+/// const NAME: &str = pretty_name::of_field!(const <std::ops::Range<u32>>::start);
+/// assert_eq!(NAME, "<Range<u32>>::start");
+/// ```
+///
+/// Tuple struct fields and union fields:
+/// ```rust
+/// struct Meters(f64);
+/// struct Pair<T>(T, T);
+/// union IntOrFloat {
+///     int: u32,
+///     float: f32,
+/// }
+/// assert_eq!(pretty_name::of_field!(Meters::0), "Meters::0");
+/// assert_eq!(pretty_name::of_field!(Pair::<u8>::1), "<Pair<u8>>::1");
+/// assert_eq!(pretty_name::of_field!(<Pair<u8>>::1), "<Pair<u8>>::1");
+/// assert_eq!(pretty_name::of_field!(IntOrFloat::float), "IntOrFloat::float");
 /// ```
 #[macro_export]
 macro_rules! of_field {
-    (Self:: $field:ident) => {{
-        let _ = |obj: Self| { let _ = &obj.$field; };
-        $crate::__with_cache!(
-            format!("{}::{}", $crate::type_name::<Self>(), stringify!($field)))
+    // IMPLEMENTATION NOTE:
+    //   - Fields are validated with `offset_of!`, which accepts named fields, numeric
+    //     fields of tuple structs, and union fields without reading any value, so no
+    //     `unsafe` block is needed for unions.
+    //   - Fields are matched as `tt` so that both identifiers and numeric indices are
+    //     accepted by the same arms.
+    (const $($path:tt)+) => {
+        $crate::__lexical_member_of!(of_field, $($path)+)
+    };
+    (Self:: $field:tt) => {{
+        let _ = ::core::mem::offset_of!(Self, $field);
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<Self>,
+            $crate::__member_name($crate::type_name::<Self>(), stringify!($field)))
     }};
-    ($ty:ident :: $field:ident) => {{
-        let _ = |obj: $ty| { let _ = &obj.$field; };
+    ($ty:ident :: $field:tt) => {{
+        let _ = ::core::mem::offset_of!($ty, $field);
         concat!(stringify!($ty), "::", stringify!($field))
     }};
-    (<$ty:ty> :: $field:ident) => {{
-        let _ = |obj: $ty| { let _ = &obj.$field; };
-        $crate::__with_cache!(
-            format!("<{}>::{}", $crate::type_name::<$ty>(), stringify!($field)))
+    ($ty:ident ::<$($arg:ty),*> :: $field:tt) => {{
+        let _ = ::core::mem::offset_of!($ty<$($arg),*>, $field);
+        $crate::__member_name_of!($ty<$($arg),*>, $field)
+    }};
+    (<$ty:ty> :: $field:tt) => {{
+        let _ = ::core::mem::offset_of!($ty, $field);
+        $crate::__member_name_of!($ty, $field)
     }};
 }
 
@@ -157,14 +698,32 @@ macro_rules! of_field {
 ///
 /// This macro resolves `Self` to the appropriate type when used inside an `impl` block.
 ///
-/// By default, this macro expects a simple type identifier like `Type::field`. To use
-/// types with qualified path or generic parameters, wrap the type in angle brackets
-/// like `<Type<T>>::field` or `<module::Type>::field`.
+/// By default, this macro expects a simple type identifier like `Type::method`. Generic
+/// types can be written in turbofish style like `Type::<T>::method`. To use types with
+/// qualified path, wrap the type in angle brackets like `<module::Type>::method`.
 /// 
 /// If both the *Type* and *method* parts are single identifiers and the *Type* part is
 /// not `Self`, the macro expands to a string literal at compile time. For more complex
 /// types, the macro uses runtime type name retrieval with caching.
 ///
+/// Prefix the path with `const` to get a string literal for complex types as well, with
+/// the same fallback to the runtime form as the `const` form of [`of_type!`], which
+/// includes trait-qualified forms like `<Type as Trait>::method`.
+///
+/// Methods from trait impls can be written in fully qualified syntax like
+/// `<Type as Trait>::method`, which yields `"<Type as Trait>::method"`. Methods declared
+/// on a trait itself, including default methods, are written like
+/// `<_ as Trait>::method`, which yields `"Trait::method"`. In both forms, the trait path
+/// and its generic arguments are truncated the same way as [`type_name`].
+///
+/// The plain spelling `Trait::method` is not supported, and cannot be: it is matched by
+/// the same rule as `Type::method`, since a macro cannot tell a trait from a type by its
+/// tokens, and no single check accepts both. Rust rejects `Trait::method` as an
+/// expression, because the implementing type is unknown (E0790), and `<dyn Trait>` only
+/// works for dyn-compatible traits without associated types. So write
+/// `of_method!(Iterator::next)` as `of_method!(<_ as Iterator>::next)`, which is
+/// validated against the trait declaration.
+///
 /// Due to implementation limitations, you cannot use the `::<..>` placeholder to exclude
 /// generic parameters. Use explicit type arguments instead.
 /// 
@@ -182,15 +741,44 @@ macro_rules! of_field {
 /// }
 /// assert_eq!(pretty_name::of_method!(MyStruct::my_method), "MyStruct::my_method");
 /// assert_eq!(pretty_name::of_method!(MyStruct::my_generic_method::<u32>), "MyStruct::my_generic_method::<u32>");
+/// assert_eq!(pretty_name::of_method!(MyGenericStruct::<u32>::my_method), "<MyGenericStruct<u32>>::my_method");
+/// assert_eq!(pretty_name::of_method!(MyGenericStruct::<u32>::my_generic_method::<String>), "<MyGenericStruct<u32>>::my_generic_method::<String>");
 /// assert_eq!(pretty_name::of_method!(<MyGenericStruct<u32>>::my_method), "<MyGenericStruct<u32>>::my_method");
 /// assert_eq!(pretty_name::of_method!(<MyGenericStruct<u32>>::my_generic_method::<String>), "<MyGenericStruct<u32>>::my_generic_method::<String>");
 /// ```
+///
+/// Trait methods:
+/// ```rust
+/// use std::fmt;
+/// struct MyStruct;
+/// impl fmt::Display for MyStruct {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+/// }
+/// impl fmt::Debug for MyStruct {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+/// }
+/// assert_eq!(pretty_name::of_method!(<MyStruct as fmt::Display>::fmt), "<MyStruct as Display>::fmt");
+/// assert_eq!(pretty_name::of_method!(<MyStruct as std::fmt::Debug>::fmt), "<MyStruct as Debug>::fmt");
+/// assert_eq!(pretty_name::of_method!(<Vec<u8> as IntoIterator>::into_iter), "<Vec<u8> as IntoIterator>::into_iter");
+/// assert_eq!(pretty_name::of_method!(<_ as Iterator>::next), "Iterator::next");
+/// assert_eq!(pretty_name::of_method!(<_ as Iterator>::size_hint), "Iterator::size_hint");
+/// assert_eq!(pretty_name::of_method!(<_ as Into<String>>::into), "<Into<String>>::into");
+/// ```
+///
+/// Methods declared on a trait need the `<_ as Trait>` form:
+/// ```compile_fail
+/// let _ = pretty_name::of_method!(Iterator::next);
+/// ```
 #[macro_export]
 macro_rules! of_method {
+    (const $($path:tt)+) => {
+        $crate::__lexical_member_of!(of_method, $($path)+)
+    };
     (Self:: $method:ident) => {{
         let _ = &Self::$method;
-        $crate::__with_cache!(
-            format!("{}::{}", $crate::type_name::<Self>(), stringify!($method)))
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<Self>,
+            $crate::__member_name($crate::type_name::<Self>(), stringify!($method)))
     }};
     ($ty:ident :: $method:ident) => {{
         let _ = &$ty::$method;
@@ -198,27 +786,214 @@ macro_rules! of_method {
     }};
     ($ty:ident :: $method:ident ::<$($arg:ty),*>) => {{
         let _ = &$ty::$method::<$($arg),*>;
+        $crate::__member_name_of!($ty, $method, $($arg),*)
+    }};
+
+    ($ty:ident ::<$($ty_arg:ty),*> :: $method:ident) => {{
+        let _ = &$ty::<$($ty_arg),*>::$method;
+        $crate::__member_name_of!($ty<$($ty_arg),*>, $method)
+    }};
+    ($ty:ident ::<$($ty_arg:ty),*> :: $method:ident ::<$($arg:ty),*>) => {{
+        let _ = &$ty::<$($ty_arg),*>::$method::<$($arg),*>;
+        $crate::__member_name_of!($ty<$($ty_arg),*>, $method, $($arg),*)
+    }};
+
+    (<_ as $trait:path> :: $method:ident) => {{
+        #[allow(unused)] fn check<T: $trait>() { let _ = &<T as $trait>::$method; }
         $crate::__with_cache!(
+            $crate::__member_name(
+                &$crate::__truncate_type_name(stringify!($trait)),
+                stringify!($method)))
+    }};
+    (<_ as $trait:path> :: $method:ident ::<$($arg:ty),*>) => {{
+        #[allow(unused)] fn check<T: $trait>() {
+            let _ = &<T as $trait>::$method::<$($arg),*>;
+        }
+        $crate::__with_type_cache!(
+            ($(::core::marker::PhantomData<$arg>,)*),
             format!(
-                "{}::{}::<{}>",
+                "{}::<{}>",
+                $crate::__member_name(
+                    &$crate::__truncate_type_name(stringify!($trait)),
+                    stringify!($method)),
+                vec![$($crate::type_name::<$arg>()),*].join(", ")))
+    }};
+    (<$ty:ty as $trait:path> :: $method:ident) => {{
+        let _ = &<$ty as $trait>::$method;
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<$ty>,
+            format!(
+                "<{} as {}>::{}",
+                $crate::type_name::<$ty>(),
+                $crate::__truncate_type_name(stringify!($trait)),
+                stringify!($method)))
+    }};
+    (<$ty:ty as $trait:path> :: $method:ident ::<$($arg:ty),*>) => {{
+        let _ = &<$ty as $trait>::$method::<$($arg),*>;
+        $crate::__with_type_cache!(
+            (::core::marker::PhantomData<$ty>, $(::core::marker::PhantomData<$arg>),*),
+            format!(
+                "<{} as {}>::{}::<{}>",
                 $crate::type_name::<$ty>(),
+                $crate::__truncate_type_name(stringify!($trait)),
                 stringify!($method),
                 vec![$($crate::type_name::<$arg>()),*].join(", ")))
     }};
 
     (<$ty:ty> :: $method:ident) => {{
         let _ = &<$ty>::$method;
-        $crate::__with_cache!(
-            format!("<{}>::{}", $crate::type_name::<$ty>(), stringify!($method)))
+        $crate::__member_name_of!($ty, $method)
     }};
     (<$ty:ty> :: $method:ident ::<$($arg:ty),*>) => {{
         let _ = &<$ty>::$method::<$($arg),*>;
+        $crate::__member_name_of!($ty, $method, $($arg),*)
+    }};
+}
+
+/// Get the name of the given associated constant like `Type::CONST` as a `&'static str`.
+///
+/// This macro resolves `Self` to the appropriate type when used inside an `impl` block.
+///
+/// Like [`of_method!`], generic types can be written like `Type::<T>::CONST` or
+/// `<Type<T>>::CONST`, constants from trait impls like `<Type as Trait>::CONST`, and
+/// constants declared on a trait itself like `<_ as Trait>::CONST`.
+///
+/// If the *Type* part is a single identifier and is not `Self`, the macro expands to a
+/// string literal at compile time. For more complex types, the macro uses runtime type
+/// name retrieval with caching.
+///
+/// Prefix the path with `const` to get a string literal for complex types as well, with
+/// the same fallback to the runtime form as the `const` form of [`of_type!`], which
+/// includes trait-qualified forms like `<Type as Trait>::CONST`.
+///
+/// # Examples
+/// ```rust
+/// trait Limits {
+///     const MAX: u32;
+/// }
+/// struct Config;
+/// impl Config {
+///     const DEFAULT_PORT: u16 = 8080;
+/// }
+/// impl Limits for Config {
+///     const MAX: u32 = 16;
+/// }
+/// struct Wrapper<T>(T);
+/// impl<T> Wrapper<T> {
+///     const SIZE: usize = std::mem::size_of::<T>();
+/// }
+/// assert_eq!(pretty_name::of_assoc_const!(Config::DEFAULT_PORT), "Config::DEFAULT_PORT");
+/// assert_eq!(pretty_name::of_assoc_const!(Wrapper::<u32>::SIZE), "<Wrapper<u32>>::SIZE");
+/// assert_eq!(pretty_name::of_assoc_const!(<Wrapper<u32>>::SIZE), "<Wrapper<u32>>::SIZE");
+/// assert_eq!(pretty_name::of_assoc_const!(<Config as Limits>::MAX), "<Config as Limits>::MAX");
+/// assert_eq!(pretty_name::of_assoc_const!(<_ as Limits>::MAX), "Limits::MAX");
+/// ```
+#[macro_export]
+macro_rules! of_assoc_const {
+    (const $($path:tt)+) => {
+        $crate::__lexical_member_of!(of_assoc_const, $($path)+)
+    };
+    (Self:: $const:ident) => {{
+        let _ = &Self::$const;
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<Self>,
+            $crate::__member_name($crate::type_name::<Self>(), stringify!($const)))
+    }};
+    ($ty:ident :: $const:ident) => {{
+        let _ = &$ty::$const;
+        concat!(stringify!($ty), "::", stringify!($const))
+    }};
+    ($ty:ident ::<$($arg:ty),*> :: $const:ident) => {{
+        let _ = &$ty::<$($arg),*>::$const;
+        $crate::__member_name_of!($ty<$($arg),*>, $const)
+    }};
+    (<_ as $trait:path> :: $const:ident) => {{
+        #[allow(unused)] fn check<T: $trait>() { let _ = &<T as $trait>::$const; }
         $crate::__with_cache!(
+            $crate::__member_name(
+                &$crate::__truncate_type_name(stringify!($trait)),
+                stringify!($const)))
+    }};
+    (<$ty:ty as $trait:path> :: $const:ident) => {{
+        let _ = &<$ty as $trait>::$const;
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<$ty>,
             format!(
-                "<{}>::{}::<{}>",
+                "<{} as {}>::{}",
                 $crate::type_name::<$ty>(),
-                stringify!($method),
-                vec![$($crate::type_name::<$arg>()),*].join(", ")))
+                $crate::__truncate_type_name(stringify!($trait)),
+                stringify!($const)))
+    }};
+    (<$ty:ty> :: $const:ident) => {{
+        let _ = &<$ty>::$const;
+        $crate::__member_name_of!($ty, $const)
+    }};
+}
+
+/// Get the name of the given associated type like `<Type as Trait>::Assoc` as a
+/// `&'static str`.
+///
+/// This macro resolves `Self` to the appropriate type when used inside an `impl` block,
+/// so `Self::Assoc` can be used inside trait impls.
+///
+/// Associated types from trait impls are written like `<Type as Trait>::Assoc`, and
+/// associated types declared on a trait itself like `<_ as Trait>::Assoc`. The trait
+/// path and its generic arguments are truncated the same way as [`type_name`].
+///
+/// Prefix the associated type with `resolved` to get the name of the concrete type the
+/// projection resolves to instead, like `resolved <Type as Trait>::Assoc`.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(pretty_name::of_assoc_type!(<Vec<u8> as IntoIterator>::Item), "<Vec<u8> as IntoIterator>::Item");
+/// assert_eq!(pretty_name::of_assoc_type!(<_ as IntoIterator>::IntoIter), "IntoIterator::IntoIter");
+/// assert_eq!(pretty_name::of_assoc_type!(resolved <Vec<u8> as IntoIterator>::Item), "u8");
+/// assert_eq!(pretty_name::of_assoc_type!(resolved <Vec<u8> as IntoIterator>::IntoIter), "IntoIter<u8>");
+///
+/// struct Counter;
+/// impl Iterator for Counter {
+///     type Item = u32;
+///     fn next(&mut self) -> Option<Self::Item> {
+///         assert_eq!(pretty_name::of_assoc_type!(Self::Item), "Counter::Item");
+///         assert_eq!(pretty_name::of_assoc_type!(resolved Self::Item), "u32");
+///         None
+///     }
+/// }
+/// Counter.next();
+/// ```
+#[macro_export]
+macro_rules! of_assoc_type {
+    (resolved Self:: $assoc:ident) => {{
+        $crate::type_name::<Self::$assoc>()
+    }};
+    (resolved <$ty:ty as $trait:path> :: $assoc:ident) => {{
+        $crate::type_name::<<$ty as $trait>::$assoc>()
+    }};
+
+    (Self:: $assoc:ident) => {{
+        let _ = ::core::marker::PhantomData::<Self::$assoc>;
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<Self>,
+            $crate::__member_name($crate::type_name::<Self>(), stringify!($assoc)))
+    }};
+    (<_ as $trait:path> :: $assoc:ident) => {{
+        #[allow(unused)] fn check<T: ?Sized + $trait>() {
+            let _ = ::core::marker::PhantomData::<<T as $trait>::$assoc>;
+        }
+        $crate::__with_cache!(
+            $crate::__member_name(
+                &$crate::__truncate_type_name(stringify!($trait)),
+                stringify!($assoc)))
+    }};
+    (<$ty:ty as $trait:path> :: $assoc:ident) => {{
+        let _ = ::core::marker::PhantomData::<<$ty as $trait>::$assoc>;
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<$ty>,
+            format!(
+                "<{} as {}>::{}",
+                $crate::type_name::<$ty>(),
+                $crate::__truncate_type_name(stringify!($trait)),
+                stringify!($assoc)))
     }};
 }
 
@@ -233,10 +1008,14 @@ macro_rules! of_method {
 /// string literal at compile time. For more complex types, the macro uses runtime type
 /// name retrieval with caching.
 ///
-/// This macro currently expects only simple type identifiers.
-/// Support for more complex types requires the experimental feature `more_qualified_paths`
-/// (issue #86935 <https://github.com/rust-lang/rust/issues/86935>) to be stabilized (or
-/// enabled via `#![feature(more_qualified_paths)]` if using a nightly compiler).
+/// Prefix the path with `const` to get a string literal for complex types as well, with
+/// the same fallback to the runtime form as the `const` form of [`of_type!`].
+///
+/// Generic enums can be written in turbofish style like `Type::<T>::Variant`. Support
+/// for qualified paths like `<module::Type>::Variant` requires the experimental feature
+/// `more_qualified_paths` (issue #86935 <https://github.com/rust-lang/rust/issues/86935>)
+/// to be stabilized (or enabled via `#![feature(more_qualified_paths)]` if using a
+/// nightly compiler).
 /// 
 /// # Examples
 /// ```rust
@@ -248,23 +1027,38 @@ macro_rules! of_method {
 /// assert_eq!(pretty_name::of_variant!(MyEnum::UnitVariant), "MyEnum::UnitVariant");
 /// assert_eq!(pretty_name::of_variant!(MyEnum::TupleVariant(..)), "MyEnum::TupleVariant");
 /// assert_eq!(pretty_name::of_variant!(MyEnum::StructVariant {..}), "MyEnum::StructVariant");
+///
+/// enum MyGenericEnum<T> {
+///     UnitVariant,
+///     TupleVariant(T),
+///     StructVariant { field: T },
+/// }
+/// assert_eq!(pretty_name::of_variant!(MyGenericEnum::<u32>::UnitVariant), "<MyGenericEnum<u32>>::UnitVariant");
+/// assert_eq!(pretty_name::of_variant!(MyGenericEnum::<u32>::TupleVariant(..)), "<MyGenericEnum<u32>>::TupleVariant");
+/// assert_eq!(pretty_name::of_variant!(MyGenericEnum::<u32>::StructVariant {..}), "<MyGenericEnum<u32>>::StructVariant");
 /// ```
 #[macro_export]
 macro_rules! of_variant {
+    (const $($path:tt)+) => {
+        $crate::__lexical_member_of!(of_variant, $($path)+)
+    };
     (Self:: $variant:ident) => {{
         let _ = |obj: Self| match obj { Self::$variant => {}, _ => {} };
-        $crate::__with_cache!(
-            format!("{}::{}", $crate::type_name::<Self>(), stringify!($variant)))
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<Self>,
+            $crate::__member_name($crate::type_name::<Self>(), stringify!($variant)))
     }};
     (Self:: $variant:ident (..)) => {{
         let _ = |obj: Self| match obj { Self::$variant(..) => {}, _ => {} };
-        $crate::__with_cache!(
-            format!("{}::{}", $crate::type_name::<Self>(), stringify!($variant)))
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<Self>,
+            $crate::__member_name($crate::type_name::<Self>(), stringify!($variant)))
     }};
     (Self:: $variant:ident {..}) => {{
         let _ = |obj: Self| match obj { Self::$variant { .. } => {}, _ => {} };
-        $crate::__with_cache!(
-            format!("{}::{}", $crate::type_name::<Self>(), stringify!($variant)))
+        $crate::__with_type_cache!(
+            ::core::marker::PhantomData<Self>,
+            $crate::__member_name($crate::type_name::<Self>(), stringify!($variant)))
     }};
 
     ($ty:ident :: $variant:ident) => {{
@@ -280,20 +1074,36 @@ macro_rules! of_variant {
         concat!(stringify!($ty), "::", stringify!($variant))
     }};
 
+    ($ty:ident ::<$($arg:ty),*> :: $variant:ident) => {{
+        let _ = |obj: $ty<$($arg),*>| match obj {
+            $ty::<$($arg),*>::$variant => {}, _ => {}
+        };
+        $crate::__member_name_of!($ty<$($arg),*>, $variant)
+    }};
+    ($ty:ident ::<$($arg:ty),*> :: $variant:ident (..)) => {{
+        let _ = |obj: $ty<$($arg),*>| match obj {
+            $ty::<$($arg),*>::$variant(..) => {}, _ => {}
+        };
+        $crate::__member_name_of!($ty<$($arg),*>, $variant)
+    }};
+    ($ty:ident ::<$($arg:ty),*> :: $variant:ident {..}) => {{
+        let _ = |obj: $ty<$($arg),*>| match obj {
+            $ty::<$($arg),*>::$variant { .. } => {}, _ => {}
+        };
+        $crate::__member_name_of!($ty<$($arg),*>, $variant)
+    }};
+
     (<$ty:ty> :: $variant:ident) => {{
         let _ = |obj: $ty| match obj { <$ty>::$variant => {}, _ => {} };
-        $crate::__with_cache!(
-            format!("<{}>::{}", $crate::type_name::<$ty>(), stringify!($variant)))
+        $crate::__member_name_of!($ty, $variant)
     }};
     (<$ty:ty> :: $variant:ident (..)) => {{
         let _ = |obj: $ty| match obj { <$ty>::$variant(..) => {}, _ => {} };
-        $crate::__with_cache!(
-            format!("<{}>::{}", $crate::type_name::<$ty>(), stringify!($variant)))
+        $crate::__member_name_of!($ty, $variant)
     }};
     (<$ty:ty> :: $variant:ident {..}) => {{
         let _ = |obj: $ty| match obj { <$ty>::$variant { .. } => {}, _ => {} };
-        $crate::__with_cache!(
-            format!("<{}>::{}", $crate::type_name::<$ty>(), stringify!($variant)))
+        $crate::__member_name_of!($ty, $variant)
     }};
 }
 
@@ -313,3 +1123,395 @@ macro_rules! of_variant {
     let my_struct = MyStruct { my_field: 42 };
     my_struct.test();
 }
+
+#[test] fn test_generic_types() {
+    use std::collections::HashMap;
+    #[allow(dead_code)]
+    struct MyGenericStruct<T>(std::marker::PhantomData<T>);
+
+    assert_eq!(of_type!(Vec), "Vec");
+    assert_eq!(of_type!(HashMap), "HashMap");
+    assert_eq!(of_type!(MyGenericStruct), "MyGenericStruct");
+    assert_eq!(of_type!(Vec<u8>), "Vec<u8>");
+}
+
+#[test] fn test_generic_traits() {
+    #[allow(dead_code)]
+    trait Codec<T> {
+        fn encode<U>(&self, value: T, extra: U);
+    }
+
+    fn codec_name<T>() -> &'static str {
+        of_trait!(Codec<T>)
+    }
+    fn boxed_codec_name<T: ?Sized>() -> &'static str {
+        of_trait!(<Codec<Box<T>>>)
+    }
+
+    assert_eq!(codec_name::<u8>(), "Codec<T>");
+    assert_eq!(boxed_codec_name::<str>(), "Codec<Box<T>>");
+    assert_eq!(of_trait!(Codec<u8>), "Codec<u8>");
+}
+
+#[test] fn test_trait_methods() {
+    struct MyStruct;
+    impl MyStruct {
+        fn test(&self) {
+            assert_eq!(of_method!(<Self as Clone>::clone), "<MyStruct as Clone>::clone");
+            assert_eq!(of_method!(<Self as Default>::default), "<MyStruct as Default>::default");
+        }
+    }
+    impl Clone for MyStruct {
+        fn clone(&self) -> Self {
+            MyStruct
+        }
+    }
+    impl Default for MyStruct {
+        fn default() -> Self {
+            MyStruct
+        }
+    }
+
+    MyStruct.test();
+    assert_eq!(of_method!(<MyStruct as Clone>::clone_from), "<MyStruct as Clone>::clone_from");
+    assert_eq!(of_method!(<_ as Iterator>::next), "Iterator::next");
+}
+
+#[test] fn test_trait_default_methods() {
+    trait Handler {
+        fn call(&self);
+        fn name(&self) -> &'static str {
+            of_method!(<Self as Handler>::call)
+        }
+    }
+    struct A;
+    struct B;
+    impl Handler for A {
+        fn call(&self) {}
+    }
+    impl Handler for B {
+        fn call(&self) {}
+    }
+    fn generic_name<T>() -> &'static str {
+        of_method!(Vec::<T>::len)
+    }
+
+    assert_eq!(A.name(), "<A as Handler>::call");
+    assert_eq!(B.name(), "<B as Handler>::call");
+    assert_eq!(A.name(), "<A as Handler>::call");
+    assert_eq!(generic_name::<u8>(), "<Vec<u8>>::len");
+    assert_eq!(generic_name::<u16>(), "<Vec<u16>>::len");
+}
+
+#[test] fn test_generic_assoc_items() {
+    trait Limits {
+        const MAX: u32;
+        type Unit;
+        fn names() -> [&'static str; 2] {
+            [of_assoc_const!(Self::MAX), of_assoc_type!(Self::Unit)]
+        }
+    }
+    struct A;
+    struct B;
+    impl Limits for A {
+        const MAX: u32 = 1;
+        type Unit = u8;
+    }
+    impl Limits for B {
+        const MAX: u32 = 2;
+        type Unit = u16;
+    }
+    fn names<T: Limits>() -> [&'static str; 2] {
+        [of_assoc_const!(<T as Limits>::MAX), of_assoc_type!(<T as Limits>::Unit)]
+    }
+
+    assert_eq!(names::<A>(), ["<A as Limits>::MAX", "<A as Limits>::Unit"]);
+    assert_eq!(names::<B>(), ["<B as Limits>::MAX", "<B as Limits>::Unit"]);
+    assert_eq!(A::names(), ["A::MAX", "A::Unit"]);
+    assert_eq!(B::names(), ["B::MAX", "B::Unit"]);
+}
+
+#[test] fn test_canonical_names() {
+    mod my_module {
+        pub struct MyStruct {
+            pub my_field: u32,
+        }
+        impl MyStruct {
+            pub fn my_method(&self) {}
+            pub fn my_generic_method<T>(&self) {}
+        }
+        pub struct MyGenericStruct<T> {
+            pub my_field: T,
+        }
+        impl<T> MyGenericStruct<T> {
+            pub fn my_method(&self) {}
+            pub fn my_generic_method<U>(&self) {}
+        }
+        #[allow(dead_code)]
+        pub enum MyEnum {
+            Unit,
+            Tuple(()),
+            Struct { field: () },
+        }
+        #[allow(dead_code)]
+        pub enum MyGenericEnum<T> {
+            Unit,
+            Tuple(T),
+            Struct { field: T },
+        }
+    }
+    use my_module::*;
+
+    impl MyStruct {
+        fn test() {
+            assert_eq!(of_field!(Self::my_field), "MyStruct::my_field");
+            assert_eq!(of_method!(Self::my_method), "MyStruct::my_method");
+            assert_eq!(of_method!(Self::my_generic_method::<u8>), "MyStruct::my_generic_method::<u8>");
+        }
+    }
+    impl MyGenericStruct<u32> {
+        fn test() {
+            assert_eq!(of_field!(Self::my_field), "<MyGenericStruct<u32>>::my_field");
+            assert_eq!(of_method!(Self::my_method), "<MyGenericStruct<u32>>::my_method");
+            assert_eq!(of_method!(Self::my_generic_method::<u8>), "<MyGenericStruct<u32>>::my_generic_method::<u8>");
+        }
+    }
+    impl MyEnum {
+        fn test() {
+            assert_eq!(of_variant!(Self::Unit), "MyEnum::Unit");
+            assert_eq!(of_variant!(Self::Tuple(..)), "MyEnum::Tuple");
+            assert_eq!(of_variant!(Self::Struct {..}), "MyEnum::Struct");
+        }
+    }
+    impl MyGenericEnum<u32> {
+        fn test() {
+            assert_eq!(of_variant!(Self::Unit), "<MyGenericEnum<u32>>::Unit");
+            assert_eq!(of_variant!(Self::Tuple(..)), "<MyGenericEnum<u32>>::Tuple");
+            assert_eq!(of_variant!(Self::Struct {..}), "<MyGenericEnum<u32>>::Struct");
+        }
+    }
+
+    // ===== Fields =====
+    assert_eq!(of_field!(MyStruct::my_field), "MyStruct::my_field");
+    assert_eq!(of_field!(<MyStruct>::my_field), "MyStruct::my_field");
+    assert_eq!(of_field!(<my_module::MyStruct>::my_field), "MyStruct::my_field");
+    assert_eq!(of_field!(MyGenericStruct::<u32>::my_field), "<MyGenericStruct<u32>>::my_field");
+    assert_eq!(of_field!(<MyGenericStruct<u32>>::my_field), "<MyGenericStruct<u32>>::my_field");
+    assert_eq!(of_field!(<my_module::MyGenericStruct<u32>>::my_field), "<MyGenericStruct<u32>>::my_field");
+
+    // ===== Methods =====
+    assert_eq!(of_method!(MyStruct::my_method), "MyStruct::my_method");
+    assert_eq!(of_method!(<MyStruct>::my_method), "MyStruct::my_method");
+    assert_eq!(of_method!(<my_module::MyStruct>::my_method), "MyStruct::my_method");
+    assert_eq!(of_method!(MyStruct::my_generic_method::<u8>), "MyStruct::my_generic_method::<u8>");
+    assert_eq!(of_method!(<my_module::MyStruct>::my_generic_method::<u8>), "MyStruct::my_generic_method::<u8>");
+    assert_eq!(of_method!(MyGenericStruct::<u32>::my_method), "<MyGenericStruct<u32>>::my_method");
+    assert_eq!(of_method!(<MyGenericStruct<u32>>::my_method), "<MyGenericStruct<u32>>::my_method");
+    assert_eq!(of_method!(<my_module::MyGenericStruct<u32>>::my_method), "<MyGenericStruct<u32>>::my_method");
+    assert_eq!(of_method!(MyGenericStruct::<u32>::my_generic_method::<u8>), "<MyGenericStruct<u32>>::my_generic_method::<u8>");
+    assert_eq!(of_method!(<MyGenericStruct<u32>>::my_generic_method::<u8>), "<MyGenericStruct<u32>>::my_generic_method::<u8>");
+
+    // ===== Variants =====
+    assert_eq!(of_variant!(MyEnum::Unit), "MyEnum::Unit");
+    assert_eq!(of_variant!(MyEnum::Tuple(..)), "MyEnum::Tuple");
+    assert_eq!(of_variant!(MyEnum::Struct {..}), "MyEnum::Struct");
+    assert_eq!(of_variant!(MyGenericEnum::<u32>::Unit), "<MyGenericEnum<u32>>::Unit");
+    assert_eq!(of_variant!(MyGenericEnum::<u32>::Tuple(..)), "<MyGenericEnum<u32>>::Tuple");
+    assert_eq!(of_variant!(MyGenericEnum::<u32>::Struct {..}), "<MyGenericEnum<u32>>::Struct");
+
+    MyStruct::test();
+    MyGenericStruct::<u32>::test();
+    MyEnum::test();
+    MyGenericEnum::<u32>::test();
+}
+
+#[test] fn test_tuple_and_union_fields() {
+    #[allow(dead_code)]
+    struct Meters(f64);
+    #[allow(dead_code)]
+    union IntOrFloat {
+        int: u32,
+        float: f32,
+    }
+    impl Meters {
+        fn test() {
+            assert_eq!(of_field!(Self::0), "Meters::0");
+        }
+    }
+    impl IntOrFloat {
+        fn test() {
+            assert_eq!(of_field!(Self::int), "IntOrFloat::int");
+        }
+    }
+
+    assert_eq!(of_field!(Meters::0), "Meters::0");
+    assert_eq!(of_field!(<(u8, u16)>::1), "<(u8, u16)>::1");
+    assert_eq!(of_field!(IntOrFloat::float), "IntOrFloat::float");
+    Meters::test();
+    IntOrFloat::test();
+}
+
+#[test] fn test_turbofish() {
+    #[allow(dead_code)]
+    struct MyStruct<T> {
+        my_field: T,
+    }
+    impl<T> MyStruct<T> {
+        fn my_method(&self) {}
+    }
+    #[allow(dead_code)]
+    enum MyEnum<T> {
+        Unit,
+        Tuple(T),
+        Struct { field: T },
+    }
+
+    assert_eq!(of_field!(MyStruct::<u32>::my_field), "<MyStruct<u32>>::my_field");
+    assert_eq!(of_method!(MyStruct::<u32>::my_method), "<MyStruct<u32>>::my_method");
+    assert_eq!(of_variant!(MyEnum::<u32>::Unit), "<MyEnum<u32>>::Unit");
+    assert_eq!(of_variant!(MyEnum::<u32>::Tuple(..)), "<MyEnum<u32>>::Tuple");
+    assert_eq!(of_variant!(MyEnum::<String>::Struct {..}), "<MyEnum<String>>::Struct");
+}
+
+/// Every other way of truncating names must agree with [`type_name`].
+#[test] fn test_same_names() {
+    macro_rules! assert_same {
+        ($($ty:ty),* $(,)?) => {$({
+            assert_eq!(of_type!(const $ty), type_name::<$ty>());
+            assert_eq!(display::<$ty>().to_string(), type_name::<$ty>());
+            #[cfg(feature = "nightly")] {
+                const NAME: &str = const_type_name::<$ty>();
+                assert_eq!(NAME, type_name::<$ty>());
+            }
+        })*};
+    }
+
+    assert_same!(
+        i32, str, [u8], &'static str, &mut [i32], *const *mut i32,
+        [i32; 5], [[u8; 2]; 3], (), (i32,), (i32, (String, bool)),
+        Option<&str>, Result<(), ()>, std::collections::HashMap<String, Vec<i32>>,
+        std::borrow::Cow<'static, str>,
+        fn(), fn(&str) -> String, for<'a> fn(&'a u8) -> &'a u8,
+        unsafe extern "C" fn(i32), fn() -> fn(i32) -> i32,
+        Box<dyn std::fmt::Debug + Send + Sync + 'static>,
+        &dyn Fn(u8) -> u8, Box<dyn Iterator<Item = Vec<u8>>>,
+        Vec<Option<Result<Box<dyn std::fmt::Debug>, String>>>,
+        std::vec::IntoIter<std::string::String>,
+    );
+
+    // closures have names that do not parse as types, which only the display path truncates
+    fn display_of_val<T>(_: &T) -> String {
+        #[cfg(feature = "nightly")]
+        assert_eq!(const_type_name::<T>(), display::<T>().to_string());
+        display::<T>().to_string()
+    }
+    let closure = || ();
+    assert_eq!(display_of_val(&closure), "test_same_names::{{closure}}");
+    assert_eq!(display_of_val(&Some(closure)), "Option<test_same_names::{{closure}}>");
+    assert_eq!(type_name_of_val(&closure), "<error>");
+    assert_eq!(type_name_of_val(&Some(closure)), "<error>");
+}
+
+#[test] fn test_lexical() {
+    use std::collections::HashMap;
+    use std::ops::Range;
+
+    const FIELD: &str = of_field!(const <std::ops::Range<u32>>::start);
+    const METHOD: &str = of_method!(const <std::vec::Vec<u32>>::push);
+    const TYPE: &str = of_type!(const HashMap<std::string::String, Box<dyn std::error::Error>>);
+    assert_eq!(FIELD, "<Range<u32>>::start");
+    assert_eq!(METHOD, "<Vec<u32>>::push");
+    assert_eq!(TYPE, "HashMap<String, Box<dyn Error>>");
+
+    const NAMES: [&str; 5] = [
+        of_field!(const Range::<u32>::end),
+        of_method!(const Vec::<u32>::extend_from_slice),
+        of_assoc_const!(const u32::MAX),
+        of_variant!(const Option::<u8>::None),
+        of_variant!(const Option::<std::string::String>::Some(..)),
+    ];
+    assert_eq!(NAMES, [
+        of_field!(Range::<u32>::end),
+        of_method!(Vec::<u32>::extend_from_slice),
+        of_assoc_const!(u32::MAX),
+        of_variant!(Option::<u8>::None),
+        of_variant!(Option::<std::string::String>::Some(..)),
+    ]);
+
+    mod my_mod {
+        pub struct Config {
+            pub port: u16,
+        }
+        impl Config {
+            pub const DEFAULT_PORT: u16 = 8080;
+        }
+    }
+    const USER_NAMES: [&str; 3] = [
+        of_field!(const <my_mod::Config>::port),
+        of_assoc_const!(const <my_mod::Config>::DEFAULT_PORT),
+        of_type!(const Vec<my_mod::Config>),
+    ];
+    assert_eq!(USER_NAMES, ["Config::port", "Config::DEFAULT_PORT", "Vec<Config>"]);
+}
+
+/// Names that cannot be proven from the tokens fall back to the runtime form.
+#[test] fn test_lexical_fallback() {
+    assert_eq!(of_type!(const std::num::NonZeroU32), "NonZero<u32>");
+    assert_eq!(of_type!(const std::os::raw::c_int), "i32");
+    assert_eq!(of_type!(const std::io::Result<()>), "Result<(), Error>");
+    assert_eq!(of_type!(const <Vec<u8> as IntoIterator>::Item), "u8");
+    assert_eq!(of_method!(const <u8 as std::fmt::Display>::fmt), "<u8 as Display>::fmt");
+
+    struct MyStruct {
+        my_field: u32,
+    }
+    impl MyStruct {
+        fn test() {
+            assert_eq!(of_type!(const Self), "MyStruct");
+            assert_eq!(of_type!(const Vec<Self>), "Vec<MyStruct>");
+            assert_eq!(of_field!(const Self::my_field), "MyStruct::my_field");
+        }
+    }
+    MyStruct::test();
+}
+
+/// Every known location of a standard library item is named at compile time, with the
+/// same name as at runtime.
+#[test] fn test_lexical_std_paths() {
+    macro_rules! assert_lexical {
+        ($($ty:ty),* $(,)?) => {$({
+            const LEXICAL: &str = of_type!(const $ty);
+            assert_eq!(LEXICAL, type_name::<$ty>());
+        })*};
+    }
+
+    use std::collections::*;
+    use std::ffi::*;
+    use std::fmt::{Debug, Display};
+    use std::future::Future;
+    use std::num::*;
+    use std::ops::*;
+    use std::path::*;
+    use std::time::*;
+    assert_lexical!(
+        std::sync::Arc<u8>, BTreeMap<u8, u8>, std::collections::btree_map::BTreeMap<u8, u8>,
+        BTreeSet<u8>, std::collections::btree_set::BTreeSet<u8>,
+        BinaryHeap<u8>, std::collections::binary_heap::BinaryHeap<u8>,
+        std::boxed::Box<u8>, CStr, CString, std::cell::Cell<u8>, std::borrow::Cow<'static, str>,
+        Duration, HashMap<u8, u8>, std::collections::hash_map::HashMap<u8, u8>,
+        HashSet<u8>, std::collections::hash_set::HashSet<u8>, Instant,
+        LinkedList<u8>, std::collections::linked_list::LinkedList<u8>,
+        std::mem::ManuallyDrop<u8>, std::mem::MaybeUninit<u8>, std::sync::Mutex<u8>,
+        std::ptr::NonNull<u8>, NonZero<u8>, std::option::Option<u8>, OsStr, OsString,
+        Path, PathBuf, std::marker::PhantomData<u8>, std::pin::Pin<Box<u8>>,
+        Range<u8>, RangeInclusive<u8>, std::rc::Rc<u8>, std::cell::RefCell<u8>,
+        std::result::Result<u8, u8>, std::sync::RwLock<u8>, std::string::String, SystemTime,
+        std::vec::Vec<u8>, VecDeque<u8>, std::collections::vec_deque::VecDeque<u8>,
+        Wrapping<u8>,
+        Box<dyn std::any::Any>, Box<dyn std::convert::AsMut<u8>>,
+        Box<dyn std::convert::AsRef<u8>>, Box<dyn Debug>, Box<dyn Display>,
+        Box<dyn std::error::Error>, Box<dyn FnOnce()>, Box<dyn FnMut()>, Box<dyn Fn()>,
+        Box<dyn Future<Output = u8>>, Box<dyn std::iter::Iterator<Item = u8>>,
+        Box<dyn Send + Sync + Unpin>,
+    );
+}
@@ -0,0 +1,116 @@
+//! Command line filter that rewrites fully qualified Rust paths in text into the short
+//! form produced by `pretty_name::type_name`.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::ExitCode;
+
+use pretty_name::{prettify_text_with, PrettifyOptions};
+
+const USAGE: &str = "\
+Usage: pretty-name [OPTIONS] [FILE]...
+
+Rewrite fully qualified Rust type and item paths like `alloc::vec::Vec<u8>` into
+their short form like `Vec<u8>`. Reads standard input if no file or `-` is given.
+
+Options:
+    --keep-crate      Keep the first segment of every path, like `alloc::Vec<u8>`
+    --max-len <N>     Abbreviate generic arguments of names longer than N characters
+    --diff            Only print changed lines, as `-old` and `+new` pairs
+    -h, --help        Print this help
+";
+
+struct Args {
+    options: PrettifyOptions,
+    diff: bool,
+    files: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args {
+        options: PrettifyOptions::default(),
+        diff: false,
+        files: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--keep-crate" => parsed.options.keep_crate = true,
+            "--diff" => parsed.diff = true,
+            "--max-len" => {
+                let value = args.next().ok_or("missing value for `--max-len`")?;
+                let max_len = value
+                    .parse()
+                    .map_err(|_| format!("invalid value for `--max-len`: `{value}`"))?;
+                parsed.options.max_len = Some(max_len);
+            }
+            _ if arg.starts_with("--max-len=") => {
+                let value = &arg["--max-len=".len()..];
+                let max_len = value
+                    .parse()
+                    .map_err(|_| format!("invalid value for `--max-len`: `{value}`"))?;
+                parsed.options.max_len = Some(max_len);
+            }
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown option `{arg}`"));
+            }
+            _ => parsed.files.push(arg),
+        }
+    }
+    if parsed.files.is_empty() {
+        parsed.files.push("-".to_owned());
+    }
+    Ok(Some(parsed))
+}
+
+fn filter(input: &mut dyn BufRead, output: &mut dyn Write, args: &Args) -> io::Result<()> {
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if input.read_until(b'\n', &mut buf)? == 0 {
+            return Ok(());
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let pretty = prettify_text_with(&line, &args.options);
+        if !args.diff {
+            output.write_all(pretty.as_bytes())?;
+        } else if pretty != line {
+            let line = line.trim_end_matches(['\r', '\n']);
+            let pretty = pretty.trim_end_matches(['\r', '\n']);
+            writeln!(output, "-{line}\n+{pretty}")?;
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+    for file in &args.files {
+        let result = if file == "-" {
+            filter(&mut io::stdin().lock(), &mut output, &args)
+        } else {
+            File::open(file).and_then(|f| filter(&mut BufReader::new(f), &mut output, &args))
+        };
+        match result {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {file}: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
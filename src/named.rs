@@ -0,0 +1,157 @@
+use std::any::Any;
+
+use crate::type_name;
+
+/// Trait for getting the human-friendly type name of a value, implemented for all types.
+///
+/// Unlike [`type_name_of_val`](crate::type_name_of_val), the name of a trait object is
+/// the name of its concrete type, as long as the trait has `Named` as a supertrait. Make
+/// sure to call the method on the trait object itself rather than on a `Box` or reference
+/// to it, like `(*boxed).pretty_type_name()`.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::Named;
+///
+/// trait Handler: Named {}
+/// struct LoginHandler;
+/// impl Handler for LoginHandler {}
+///
+/// let handler: Box<dyn Handler> = Box::new(LoginHandler);
+/// assert_eq!((*handler).pretty_type_name(), "LoginHandler");
+/// assert_eq!(handler.pretty_type_name(), "Box<dyn Handler>");
+/// assert_eq!(pretty_name::type_name_of_val(&*handler), "dyn Handler");
+/// ```
+pub trait Named {
+    /// Get the human-friendly name of the concrete type of this value.
+    fn pretty_type_name(&self) -> &'static str;
+}
+
+impl<T: ?Sized> Named for T {
+    fn pretty_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+}
+
+/// Drop-in replacement for `dyn Any` that also knows the name of its concrete type.
+///
+/// Implemented for all `'static` types. Use `Box<dyn AnyNamed>` instead of `Box<dyn Any>`
+/// to get both downcasting and [`Named::pretty_type_name`].
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::{AnyNamed, Named};
+///
+/// let value: Box<dyn AnyNamed> = Box::new(vec![1_u8]);
+/// assert_eq!((*value).pretty_type_name(), "Vec<u8>");
+/// assert!(value.is::<Vec<u8>>());
+/// assert_eq!(value.into_any().downcast::<Vec<u8>>().ok(), Some(Box::new(vec![1_u8])));
+/// ```
+pub trait AnyNamed: Any + Named {
+    /// Upcast to `&dyn Any`.
+    fn as_any(&self) -> &dyn Any;
+    /// Upcast to `&mut dyn Any`.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    /// Upcast to `Box<dyn Any>`.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Any> AnyNamed for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl dyn AnyNamed {
+    /// Check whether the concrete type is `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Get a reference to the value if its concrete type is `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Get a mutable reference to the value if its concrete type is `T`.
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+impl dyn AnyNamed + Send {
+    /// Check whether the concrete type is `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Get a reference to the value if its concrete type is `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Get a mutable reference to the value if its concrete type is `T`.
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+impl dyn AnyNamed + Send + Sync {
+    /// Check whether the concrete type is `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Get a reference to the value if its concrete type is `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Get a mutable reference to the value if its concrete type is `T`.
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_named() {
+        trait Handler: Named {}
+        struct Login;
+        struct Logout<T>(T);
+        impl Handler for Login {}
+        impl<T> Handler for Logout<T> {}
+
+        let handlers: Vec<Box<dyn Handler + Send>> = vec![Box::new(Login), Box::new(Logout(1_u8))];
+        let names = handlers
+            .iter()
+            .map(|handler| (**handler).pretty_type_name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Login", "Logout<u8>"]);
+
+        let handler: &dyn Handler = &Login;
+        assert_eq!(handler.pretty_type_name(), "Login");
+        assert_eq!(1_u8.pretty_type_name(), "u8");
+        assert_eq!("str".pretty_type_name(), "str");
+        assert_eq!([1, 2][..].pretty_type_name(), "[i32]");
+
+        let mut value: Box<dyn AnyNamed + Send + Sync> = Box::new(String::from("value"));
+        assert_eq!((*value).pretty_type_name(), "String");
+        assert!(value.is::<String>());
+        assert!(!value.is::<&str>());
+        value.downcast_mut::<String>().unwrap().push('!');
+        assert_eq!(value.downcast_ref::<String>().map(String::as_str), Some("value!"));
+        assert_eq!(*value.into_any().downcast::<String>().unwrap(), "value!");
+    }
+}
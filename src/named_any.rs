@@ -0,0 +1,203 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+
+use crate::type_name;
+
+/// Error returned by `downcast` on [`NamedAny`] and its variants when the value is not of
+/// the requested type.
+///
+/// The original value can be recovered with [`into_inner`](Self::into_inner).
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::NamedAny;
+///
+/// let value = NamedAny::new(vec![String::from("a")]);
+/// let error = value.downcast::<Vec<u8>>().unwrap_err();
+/// assert_eq!(error.to_string(), "expected Vec<u8>, found Vec<String>");
+/// assert_eq!(error.into_inner().downcast::<Vec<String>>().unwrap(), ["a"]);
+/// ```
+pub struct DowncastError<V> {
+    expected: &'static str,
+    value: V,
+}
+
+impl<V> DowncastError<V> {
+    /// Get the name of the requested type.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Get the original value back.
+    pub fn into_inner(self) -> V {
+        self.value
+    }
+}
+
+macro_rules! named_any {
+    ($(#[$attr:meta])* $name:ident, $($bound:ident)+) => {
+        $(#[$attr])*
+        pub struct $name {
+            value: Box<dyn $($bound +)+>,
+            type_name: &'static str,
+        }
+
+        impl $name {
+            /// Wrap the given value, remembering the name of its type.
+            pub fn new<T: $($bound +)+>(value: T) -> Self {
+                Self { value: Box::new(value), type_name: type_name::<T>() }
+            }
+
+            /// Get the human-friendly name of the type of the wrapped value.
+            pub fn type_name(&self) -> &'static str {
+                self.type_name
+            }
+
+            /// Check whether the wrapped value is of type `T`.
+            pub fn is<T: Any>(&self) -> bool {
+                self.value.is::<T>()
+            }
+
+            /// Get a reference to the wrapped value if it is of type `T`.
+            pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+                self.value.downcast_ref()
+            }
+
+            /// Get a mutable reference to the wrapped value if it is of type `T`.
+            pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+                self.value.downcast_mut()
+            }
+
+            /// Take the wrapped value if it is of type `T`, or get an error naming both
+            /// types otherwise.
+            pub fn downcast<T: Any>(self) -> Result<T, DowncastError<Self>> {
+                if self.value.is::<T>() {
+                    let value = self.value.downcast::<T>().unwrap_or_else(|_| unreachable!());
+                    Ok(*value)
+                } else {
+                    Err(DowncastError { expected: type_name::<T>(), value: self })
+                }
+            }
+
+            /// Unwrap into the boxed value, forgetting the name of its type.
+            pub fn into_any(self) -> Box<dyn $($bound +)+> {
+                self.value
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.type_name)
+            }
+        }
+
+        impl fmt::Debug for DowncastError<$name> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct("DowncastError")
+                    .field("expected", &self.expected)
+                    .field("found", &self.value.type_name)
+                    .finish()
+            }
+        }
+
+        impl fmt::Display for DowncastError<$name> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "expected {}, found {}", self.expected, self.value.type_name)
+            }
+        }
+
+        impl Error for DowncastError<$name> {}
+
+        impl DowncastError<$name> {
+            /// Get the name of the type of the wrapped value.
+            pub fn found(&self) -> &'static str {
+                self.value.type_name
+            }
+        }
+    };
+}
+
+named_any! {
+    /// Type-erased box like `Box<dyn Any>` that remembers the human-friendly name of the
+    /// type of its value, for error messages when downcasting fails.
+    ///
+    /// See [`NamedAnySend`] and [`NamedAnySendSync`] for values that cross threads.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pretty_name::NamedAny;
+    ///
+    /// let mut value = NamedAny::new(vec![1_u8]);
+    /// assert_eq!(value.type_name(), "Vec<u8>");
+    /// assert_eq!(format!("{value:?}"), "NamedAny(Vec<u8>)");
+    /// value.downcast_mut::<Vec<u8>>().unwrap().push(2);
+    /// assert_eq!(value.downcast::<Vec<u8>>().unwrap(), [1, 2]);
+    /// ```
+    NamedAny, Any
+}
+
+named_any! {
+    /// [`NamedAny`] for values that are `Send`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pretty_name::NamedAnySend;
+    ///
+    /// let value = NamedAnySend::new(std::cell::Cell::new(1_u8));
+    /// let value = std::thread::spawn(move || value).join().unwrap();
+    /// assert_eq!(value.type_name(), "Cell<u8>");
+    /// ```
+    NamedAnySend, Any Send
+}
+
+named_any! {
+    /// [`NamedAny`] for values that are `Send` and `Sync`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pretty_name::NamedAnySendSync;
+    ///
+    /// let value = std::sync::Arc::new(NamedAnySendSync::new(1_u8));
+    /// let shared = value.clone();
+    /// let thread = std::thread::spawn(move || assert_eq!(shared.downcast_ref::<u8>(), Some(&1)));
+    /// thread.join().unwrap();
+    /// assert_eq!(value.type_name(), "u8");
+    /// ```
+    NamedAnySendSync, Any Send Sync
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_named_any() {
+        let value = NamedAny::new(vec![String::from("a")]);
+        assert_eq!(value.type_name(), "Vec<String>");
+        assert!(value.is::<Vec<String>>());
+        assert!(!value.is::<Vec<u8>>());
+        assert_eq!(value.downcast_ref::<Vec<u8>>(), None);
+
+        let error = value.downcast::<Vec<u8>>().unwrap_err();
+        assert_eq!(error.expected(), "Vec<u8>");
+        assert_eq!(error.found(), "Vec<String>");
+        assert_eq!(error.to_string(), "expected Vec<u8>, found Vec<String>");
+        assert_eq!(
+            format!("{error:?}"),
+            r#"DowncastError { expected: "Vec<u8>", found: "Vec<String>" }"#);
+        let value = error.into_inner();
+        assert_eq!(value.downcast::<Vec<String>>().unwrap(), ["a"]);
+
+        let value = NamedAnySend::new(Some(1_u8));
+        assert_eq!(format!("{value:?}"), "NamedAnySend(Option<u8>)");
+        let error = value.downcast::<u8>().unwrap_err();
+        assert_eq!(error.to_string(), "expected u8, found Option<u8>");
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(
+            NamedAnySendSync::new(()).downcast::<bool>().unwrap_err());
+        assert_eq!(boxed.to_string(), "expected bool, found ()");
+
+        let any = NamedAnySendSync::new("str").into_any();
+        assert_eq!(any.downcast_ref::<&str>(), Some(&"str"));
+    }
+}
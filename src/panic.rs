@@ -0,0 +1,193 @@
+//! Panic hook that prettifies type paths in panic messages and backtraces.
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::panic::PanicHookInfo;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::prettify::prettify_code;
+use crate::prettify_text;
+
+/// Install a panic hook that rewrites type and item paths in panic messages and
+/// backtraces into the short forms that [`type_name`](crate::type_name) produces.
+///
+/// The report has the same layout as the default hook, so that e.g.
+/// `<my_crate::server::Worker<tokio::net::TcpStream> as Service>::call` is
+/// printed as `<Worker<TcpStream> as Service>::call`. Backtraces are captured according
+/// to `RUST_BACKTRACE` as usual, with symbol hashes like `::h0123456789abcdef` stripped.
+///
+/// The report is printed with `eprint!`, so it is captured by the test harness like the
+/// output of the default hook. It replaces the output of the default hook. A custom hook
+/// that was installed before, e.g. for logging, is still called after the report for
+/// every panic.
+///
+/// # Examples
+/// ```rust
+/// std::panic::set_hook(Box::new(|_| eprintln!("logged")));
+/// pretty_name::panic::install_hook();
+/// ```
+pub fn install_hook() {
+    let previous = std::panic::take_hook();
+    // taking the hook again gets the default hook that replaced it
+    let default = std::panic::take_hook();
+    let previous = (!is_same_hook(&*previous, &*default)).then_some(previous);
+    std::panic::set_hook(Box::new(move |info| {
+        // the default hook prints the same for payloads that are not strings
+        let message = payload_str(info).unwrap_or("Box<dyn Any>");
+        let thread = std::thread::current();
+        let thread = thread.name().unwrap_or("<unnamed>");
+        let location = info
+            .location()
+            .map_or_else(String::new, |location| format!(" at {location}"));
+        let mut report = format!(
+            "thread '{}' panicked{}:\n{}\n",
+            thread,
+            location,
+            prettify_text(message));
+        let backtrace = Backtrace::capture();
+        match backtrace.status() {
+            BacktraceStatus::Captured => {
+                report.push_str("stack backtrace:\n");
+                report.push_str(&prettify_backtrace(&backtrace));
+            }
+            // like the default hook, only mention backtraces on the first panic
+            BacktraceStatus::Disabled if FIRST_PANIC.swap(false, Ordering::Relaxed) => report.push_str(
+                "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n"),
+            _ => {}
+        }
+        eprint!("{report}");
+        if let Some(previous) = &previous {
+            previous(info);
+        }
+    }));
+}
+
+type Hook = dyn Fn(&PanicHookInfo<'_>) + Send + Sync;
+
+/// Whether no panic has been reported by the hook yet.
+static FIRST_PANIC: AtomicBool = AtomicBool::new(true);
+
+/// Check whether the given hooks are the same closure, which tells the default hook,
+/// boxed by [`std::panic::take_hook`] from the same function every time, apart from
+/// custom ones.
+fn is_same_hook(a: &Hook, b: &Hook) -> bool {
+    std::ptr::eq(a, b)
+}
+
+/// Format the given backtrace with type paths in frame names rewritten into their short
+/// forms and symbol hashes stripped.
+///
+/// Like the default panic hook, the full backtrace is printed only if `RUST_BACKTRACE` is
+/// set to `full`. Otherwise, frames of the panic machinery and the runtime outside of
+/// `__rust_end_short_backtrace` and `__rust_begin_short_backtrace` are omitted.
+fn prettify_backtrace(backtrace: &Backtrace) -> String {
+    let full = std::env::var_os("RUST_BACKTRACE").is_some_and(|value| value == "full");
+    if full {
+        format!("{backtrace:#}")
+            .split_inclusive('\n')
+            .map(prettify_code)
+            .collect()
+    } else {
+        let mut out = short_backtrace(&backtrace.to_string());
+        out.push_str(
+            "note: Some details are omitted, \
+             run with `RUST_BACKTRACE=full` for a verbose backtrace.\n");
+        out
+    }
+}
+
+/// Keep the frames between the short backtrace markers, renumbered from zero.
+fn short_backtrace(formatted: &str) -> String {
+    let frame_index = |line: &str| {
+        let line = line.trim_start();
+        let digits = line.find(|c: char| !c.is_ascii_digit())?;
+        (digits > 0 && line[digits..].starts_with(": ")).then_some(digits)
+    };
+    let lines = formatted.split_inclusive('\n').collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .rposition(|line| line.contains("__rust_end_short_backtrace"))
+        .and_then(|marker| {
+            let next_frame = lines[marker + 1..]
+                .iter()
+                .position(|line| frame_index(line).is_some())?;
+            Some(marker + 1 + next_frame)
+        })
+        .unwrap_or(0);
+    let end = lines[start..]
+        .iter()
+        .position(|line| line.contains("__rust_begin_short_backtrace"))
+        .map_or(lines.len(), |i| start + i);
+
+    let mut out = String::new();
+    let mut index = 0;
+    for line in &lines[start..end] {
+        match frame_index(line) {
+            Some(digits) => {
+                let line = line.trim_start();
+                out.push_str(&format!("{index:>4}{}", prettify_code(&line[digits..])));
+                index += 1;
+            }
+            None => out.push_str(line),
+        }
+    }
+    out
+}
+
+fn payload_str<'a>(info: &'a PanicHookInfo) -> Option<&'a str> {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_same_hook() {
+        // the test harness may have installed a hook of its own
+        let harness = std::panic::take_hook();
+        let default = std::panic::take_hook();
+        let again = std::panic::take_hook();
+        std::panic::set_hook(harness);
+        assert!(is_same_hook(&*default, &*again));
+        let custom: Box<Hook> = Box::new(|_| {});
+        assert!(!is_same_hook(&*default, &*custom));
+    }
+
+    #[test]
+    fn test_prettify_code() {
+        assert_eq!(
+            prettify_code("<my_crate::server::Worker<tokio::net::TcpStream> as Service>::call"),
+            "<Worker<TcpStream> as Service>::call");
+        assert_eq!(
+            prettify_code("  12: my_crate::main::h0123456789abcdef\n"),
+            "  12: main\n");
+        assert_eq!(
+            prettify_code("<alloc::vec::Vec<u8> as core::ops::drop::Drop>::drop::h0123456789abcdef"),
+            "<Vec<u8> as Drop>::drop");
+        assert_eq!(
+            prettify_code("             at /rustc/library/std/src/panicking.rs:665:5\n"),
+            "             at /rustc/library/std/src/panicking.rs:665:5\n");
+
+        let formatted = [
+            "   0: std::panicking::begin_panic_handler",
+            "             at /rustc/library/std/src/panicking.rs:665:5",
+            "   1: std::sys::backtrace::__rust_end_short_backtrace",
+            "   2: my_crate::main::h0123456789abcdef",
+            "             at ./src/main.rs:3:5",
+            "   3: core::ops::function::FnOnce::call_once",
+            "   4: std::sys::backtrace::__rust_begin_short_backtrace",
+            "   5: std::rt::lang_start",
+            "",
+        ].join("\n");
+        assert_eq!(short_backtrace(&formatted), [
+            "   0: main",
+            "             at ./src/main.rs:3:5",
+            "   1: call_once",
+            "",
+        ].join("\n"));
+    }
+}
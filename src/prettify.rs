@@ -0,0 +1,293 @@
+use std::borrow::Cow;
+
+use crate::abbrev::abbreviate_to_len;
+use crate::scan::{is_ident_byte, is_ident_start, scan_path};
+use crate::truncate::truncate_type;
+use crate::type_name::format_type;
+
+/// Options for [`prettify_text_with`].
+#[derive(Clone, Debug, Default)]
+pub struct PrettifyOptions {
+    /// Keep the first segment of every path, usually the crate name, like `alloc::Vec`.
+    pub keep_crate: bool,
+    /// Abbreviate every replaced name to at most this many characters when possible, the
+    /// same way as [`type_name_abbrev`](crate::type_name_abbrev).
+    pub max_len: Option<usize>,
+}
+
+/// Replace every type path like `alloc::vec::Vec<alloc::string::String>` in the given
+/// text with its human-friendly name like `Vec<String>`.
+///
+/// A type path starts with a path of at least two segments and extends over balanced
+/// generic arguments on the same line. Each match is parsed and truncated the same way as
+/// [`type_name`](crate::type_name). Fragments that fail to parse, like
+/// `alloc::vec::Vec<{integer}>`, are left untouched as a whole, as are URLs and the
+/// surrounding text, and the text is borrowed if nothing is replaced. Symbol hashes like
+/// `::h0123456789abcdef` at the end of paths in backtraces are removed.
+///
+/// Paths of two segments without generic arguments, like `std::io` or `serde::Value`, are
+/// usually prose. Outside of angle brackets, they are only replaced if they end in a
+/// symbol hash or are followed by a suffix like `::{{closure}}`.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::prettify_text;
+/// assert_eq!(
+///     prettify_text("expected alloc::vec::Vec<core::option::Option<alloc::string::String>>, found u8"),
+///     "expected Vec<Option<String>>, found u8");
+/// assert_eq!(
+///     prettify_text("panicked at my_crate::main::{{closure}}"),
+///     "panicked at main::{{closure}}");
+/// assert!(matches!(prettify_text("nothing to do"), std::borrow::Cow::Borrowed(_)));
+/// ```
+pub fn prettify_text(text: &str) -> Cow<'_, str> {
+    prettify_text_with(text, &PrettifyOptions::default())
+}
+
+/// Same as [`prettify_text`], with the given options.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::{prettify_text_with, PrettifyOptions};
+/// let options = PrettifyOptions { keep_crate: true, max_len: Some(16) };
+/// assert_eq!(
+///     prettify_text_with("found alloc::vec::Vec<core::option::Option<u8>>", &options),
+///     "found alloc::Vec<…>");
+/// ```
+pub fn prettify_text_with<'a>(text: &'a str, options: &PrettifyOptions) -> Cow<'a, str> {
+    prettify_paths(text, options, false)
+}
+
+/// Same as [`prettify_text`], but replace every path of at least two segments, for text
+/// that is known to be code, like demangled symbols and backtrace frames.
+pub(crate) fn prettify_code(text: &str) -> Cow<'_, str> {
+    prettify_paths(text, &PrettifyOptions::default(), true)
+}
+
+/// Length of a symbol hash like `::h0123456789abcdef`.
+const HASH_LEN: usize = "::h".len() + 16;
+
+/// Check whether a symbol hash like `::h0123456789abcdef` starts at `i`.
+fn is_hash_at(bytes: &[u8], i: usize) -> bool {
+    let bytes = &bytes[i..];
+    bytes.len() >= HASH_LEN &&
+    bytes.starts_with(b"::h") &&
+    bytes[3..HASH_LEN].iter().all(u8::is_ascii_hexdigit) &&
+    bytes.get(HASH_LEN).is_none_or(|&byte| !is_ident_byte(byte))
+}
+
+/// Replace the type paths in the given text, and remove symbol hashes.
+fn prettify_paths<'a>(text: &'a str, options: &PrettifyOptions, every_path: bool) -> Cow<'a, str> {
+    let bytes = text.as_bytes();
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        if is_hash_at(bytes, i) {
+            out.push_str(&text[copied..i]);
+            copied = i + HASH_LEN;
+            i = copied;
+            continue;
+        }
+        if text[i..].starts_with("://") {
+            // a URL like `https://docs.rs/foo::bar`, whose paths are not types
+            i = text[i..].find(char::is_whitespace).map_or(text.len(), |len| i + len);
+            continue;
+        }
+        let is_path_start =
+            is_ident_start(bytes[i]) &&
+            (i == 0 || !is_ident_byte(bytes[i - 1]) && bytes[i - 1] != b':');
+        if !is_path_start {
+            depth = angle_depth(bytes, i, depth);
+            i += 1;
+            continue;
+        }
+        let (path_end, last_segment) = scan_path(bytes, i);
+        if last_segment > i && is_hash_at(bytes, last_segment - 2) {
+            // the path of a symbol, like a backtrace frame
+            let symbol = &text[i..last_segment - 2];
+            let pretty = prettify_fragment(symbol, options);
+            out.push_str(&text[copied..i]);
+            out.push_str(pretty.as_deref().unwrap_or(symbol));
+            copied = path_end;
+            i = path_end;
+            continue;
+        }
+        if last_segment == i {
+            i = path_end;
+            continue;
+        }
+        let end = match scan_generics(bytes, path_end) {
+            Some(end) => end,
+            // unbalanced generic arguments, which do not belong to a type
+            None if bytes.get(path_end) == Some(&b'<') => {
+                i = path_end;
+                continue;
+            }
+            None => path_end,
+        };
+        if end == path_end && !every_path && depth == 0 && !is_code_path(text, i, path_end) {
+            i = path_end;
+            continue;
+        }
+        // a fragment is replaced as a whole or not at all
+        if let Some(pretty) = prettify_fragment(&text[i..end], options) {
+            out.push_str(&text[copied..i]);
+            out.push_str(&pretty);
+            copied = end;
+        }
+        i = end;
+    }
+    if copied == 0 {
+        Cow::Borrowed(text)
+    } else {
+        out.push_str(&text[copied..]);
+        Cow::Owned(out)
+    }
+}
+
+/// Get the depth of the angle brackets enclosing the text after the byte at `i`, given
+/// the depth before it.
+///
+/// Paths inside angle brackets, like `my::Service` in `<Worker as my::Service>::call`,
+/// are in a type or qualified path rather than prose. Type names never put whitespace
+/// inside their brackets, so comparisons like `a < b` and arrows like `->` are not taken
+/// for brackets, and every line starts outside of them.
+fn angle_depth(bytes: &[u8], i: usize, depth: usize) -> usize {
+    let is_tight = |j: Option<usize>| j
+        .and_then(|j| bytes.get(j))
+        .is_some_and(|&byte| !byte.is_ascii_whitespace() && byte != b'=');
+    match bytes[i] {
+        b'<' if is_tight(Some(i + 1)) => depth + 1,
+        b'>' if is_tight(i.checked_sub(1)) && bytes[i - 1] != b'-' => depth.saturating_sub(1),
+        b'\n' => 0,
+        _ => depth,
+    }
+}
+
+/// Check whether the path without generic arguments between `start` and `end` is code
+/// rather than prose like `std::io` or `serde::Value`.
+///
+/// Outside of angle brackets, paths of two segments are only taken for code if they are
+/// followed by a suffix like `::{{closure}}` that only occurs in code.
+fn is_code_path(text: &str, start: usize, end: usize) -> bool {
+    text[start..end].matches("::").count() >= 2 || text[end..].starts_with("::")
+}
+
+/// Scan balanced generic arguments like `<T, U>` starting at `start`, and get their end.
+pub(crate) fn scan_generics(bytes: &[u8], start: usize) -> Option<usize> {
+    if bytes.get(start) != Some(&b'<') {
+        return None;
+    }
+    let mut stack = Vec::new();
+    for (i, &byte) in bytes.iter().enumerate().skip(start) {
+        match byte {
+            b'<' | b'(' | b'[' => stack.push(byte),
+            b'>' if bytes[i - 1] == b'-' => {}
+            b'>' | b')' | b']' => {
+                let open = match byte {
+                    b'>' => b'<',
+                    b')' => b'(',
+                    _ => b'[',
+                };
+                if stack.pop() != Some(open) {
+                    return None;
+                }
+                if stack.is_empty() {
+                    return Some(i + 1);
+                }
+            }
+            b'{' | b'\n' | b'\r' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Truncate the given fragment if it parses as a type.
+fn prettify_fragment(fragment: &str, options: &PrettifyOptions) -> Option<String> {
+    let mut ty = syn::parse_str::<syn::Type>(fragment).ok()?;
+    truncate_type(&mut ty, options.keep_crate);
+    let pretty = format_type(&ty);
+    match options.max_len {
+        Some(max_len) => Some(abbreviate_to_len(&pretty, max_len)),
+        None => Some(pretty),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_prettify_text() {
+        assert_eq!(
+            prettify_text("PhantomData<alloc::vec::Vec<u8>>"),
+            "PhantomData<Vec<u8>>");
+        assert_eq!(
+            prettify_text("a std::collections::HashMap<alloc::string::String, (u8, &'static str)> b"),
+            "a HashMap<String, (u8, &str)> b");
+        assert_eq!(
+            prettify_text("<alloc::vec::Vec<u8> as core::iter::IntoIterator>::into_iter"),
+            "<Vec<u8> as IntoIterator>::into_iter");
+        assert_eq!(
+            prettify_text("alloc::boxed::Box<dyn core::ops::Fn(u8) -> u8 + core::marker::Send>"),
+            "Box<dyn Fn(u8) -> u8 + Send>");
+        assert_eq!(
+            prettify_text("one alloc::string::String, two core::option::Option<i32>."),
+            "one String, two Option<i32>.");
+
+        // unbalanced or unparseable fragments are left untouched as a whole
+        assert_eq!(prettify_text("alloc::vec::Vec<u8"), "alloc::vec::Vec<u8");
+        assert_eq!(prettify_text("alloc::vec::Vec<{integer}>"), "alloc::vec::Vec<{integer}>");
+        assert_eq!(
+            prettify_text("expected alloc::vec::Vec<{integer}>, found alloc::string::String"),
+            "expected alloc::vec::Vec<{integer}>, found String");
+
+        // paths in URLs are left untouched
+        assert_eq!(prettify_text("https://docs.rs/foo::bar::baz"), "https://docs.rs/foo::bar::baz");
+        assert_eq!(
+            prettify_text("see https://docs.rs/foo::bar::baz for alloc::string::String"),
+            "see https://docs.rs/foo::bar::baz for String");
+
+        let options = PrettifyOptions { keep_crate: true, max_len: None };
+        assert_eq!(
+            prettify_text_with("my_crate::handlers::Login<alloc::string::String>", &options),
+            "my_crate::Login<alloc::String>");
+        let options = PrettifyOptions { keep_crate: false, max_len: Some(11) };
+        assert_eq!(
+            prettify_text_with("alloc::vec::Vec<alloc::vec::Vec<u8>> and core::option::Option<u8>", &options),
+            "Vec<Vec<…>> and Option<u8>");
+
+        // symbol hashes of backtrace frames are removed before truncation
+        assert_eq!(
+            prettify_text("   3: core::panicking::panic_fmt::h0123456789abcdef"),
+            "   3: panic_fmt");
+        assert_eq!(prettify_text("a::h0123"), "a::h0123");
+        assert_eq!(prettify_text("a::b::h0123456789abcdefg"), "h0123456789abcdefg");
+        assert_eq!(prettify_code("  12: my_crate::main\n"), "  12: main\n");
+
+        // paths of two segments in prose are left untouched
+        assert_eq!(prettify_text("see std::io for details"), "see std::io for details");
+        assert_eq!(prettify_text("see tokio::net for details"), "see tokio::net for details");
+        assert_eq!(prettify_text("upgrade to serde::Value, not serde_json::Value"), "upgrade to serde::Value, not serde_json::Value");
+        assert_eq!(prettify_text("panicked at my_crate::main::{{closure}}"), "panicked at main::{{closure}}");
+        assert_eq!(prettify_text("my_crate::Wrapper<u8>"), "Wrapper<u8>");
+
+        // paths of two segments in types and qualified paths are replaced
+        assert_eq!(
+            prettify_text("<my_crate::Worker<u8> as my::Service>::call"),
+            "<Worker<u8> as Service>::call");
+        assert_eq!(
+            prettify_text("<Worker<…> as my::Service>::call"),
+            "<Worker<…> as Service>::call");
+        assert_eq!(prettify_text("if a < b use std::io"), "if a < b use std::io");
+        assert_eq!(prettify_text("<a\nsee std::io"), "<a\nsee std::io");
+
+        // text without type paths is borrowed
+        for text in ["", "a::", "::a", "a:b", "1::2", "std", "Vec<u8>"] {
+            assert!(matches!(prettify_text(text), std::borrow::Cow::Borrowed(_)), "{text}");
+        }
+    }
+}
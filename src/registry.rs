@@ -0,0 +1,494 @@
+//! Global registry of human-friendly type names keyed by [`TypeId`].
+//!
+//! Type-erased values like `Box<dyn Any>` only expose their [`TypeId`]. Registering the
+//! types in advance allows getting their names back for display:
+//!
+//! ```rust
+//! use std::any::Any;
+//! use pretty_name::registry;
+//!
+//! struct Position(f32, f32);
+//! registry::register::<Position>();
+//!
+//! let component: Box<dyn Any> = Box::new(Position(0.0, 0.0));
+//! assert_eq!(registry::name_of_val(&*component), Some("Position"));
+//! ```
+//!
+//! With the `derive` feature enabled, non-generic types deriving
+//! [`PrettyName`](crate::PrettyName) are registered automatically in both directions
+//! before the first lookup or registration. If two of them share a name, only one of
+//! them can be found by name.
+
+use std::any::{Any, TypeId};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use crate::type_name;
+
+static REGISTRY: RwLock<BTreeMap<TypeId, &'static str>> = RwLock::new(BTreeMap::new());
+
+/// Register the human-friendly name of given type `T`, and get the name.
+///
+/// Registering the same type again has no effect.
+pub fn register<T: ?Sized + 'static>() -> &'static str {
+    let type_id = TypeId::of::<T>();
+    let registered = REGISTRY
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(&type_id)
+        .copied();
+    if let Some(name) = registered {
+        return name;
+    }
+    let name = type_name::<T>();
+    REGISTRY
+        .write()
+        .unwrap_or_else(|err| err.into_inner())
+        .entry(type_id)
+        .or_insert(name)
+}
+
+/// Get the human-friendly name of the registered type with the given [`TypeId`].
+pub fn name_of_type_id(type_id: TypeId) -> Option<&'static str> {
+    register_derived();
+    REGISTRY
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(&type_id)
+        .copied()
+}
+
+/// Get the human-friendly name of the registered type of the given value.
+///
+/// Take care to pass `&*boxed` rather than `&boxed` for a `Box<dyn Any>`, or the name of
+/// the box itself is looked up.
+pub fn name_of_val(value: &dyn Any) -> Option<&'static str> {
+    name_of_type_id(value.type_id())
+}
+
+/// Get the [`TypeId`]s and human-friendly names of all registered types, ordered by
+/// [`TypeId`].
+pub fn registered_types() -> Vec<(TypeId, &'static str)> {
+    register_derived();
+    REGISTRY
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .map(|(&type_id, &name)| (type_id, name))
+        .collect()
+}
+
+/// Error when registering a type whose human-friendly name is already taken by another
+/// type, which happens when types from different modules share their last path segment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NameCollision {
+    /// The human-friendly name shared by both types.
+    pub name: &'static str,
+    /// The [`TypeId`] of the type registered first.
+    pub existing: TypeId,
+    /// The [`TypeId`] of the type being registered.
+    pub new: TypeId,
+}
+
+impl fmt::Display for NameCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "another type is already registered with the name `{}`", self.name)
+    }
+}
+
+impl std::error::Error for NameCollision {}
+
+type Constructor = Arc<dyn Fn() -> Box<dyn Any + Send> + Send + Sync>;
+
+/// Entry of the reverse registry.
+struct NamedType {
+    type_id: TypeId,
+    constructor: Option<Constructor>,
+    /// Deserialization into each target type, keyed by the [`TypeId`] of the target.
+    #[cfg(feature = "serde")]
+    deserialize: BTreeMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+static NAMES: RwLock<BTreeMap<&'static str, NamedType>> = RwLock::new(BTreeMap::new());
+
+/// Register the human-friendly name of given type `T` in both directions, so that it can
+/// also be found by [`type_id_of_name`].
+///
+/// # Errors
+/// Returns [`NameCollision`] if another type with the same name has been registered in
+/// the reverse direction.
+pub fn try_register<T: ?Sized + 'static>() -> Result<&'static str, NameCollision> {
+    register_derived();
+    register_named::<T>(|_| {})
+}
+
+/// Register given type `T` in both directions with a constructor, so that it can be built
+/// from its name with [`construct`].
+///
+/// Registering the same type again replaces its constructor.
+///
+/// # Errors
+/// Returns [`NameCollision`] if another type with the same name has been registered in
+/// the reverse direction.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::registry;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct RetryPolicy { attempts: u32 }
+/// registry::register_constructor(|| RetryPolicy { attempts: 3 }).unwrap();
+///
+/// let policy = registry::construct("RetryPolicy").unwrap();
+/// assert_eq!(policy.downcast_ref(), Some(&RetryPolicy { attempts: 3 }));
+/// ```
+pub fn register_constructor<T: Any + Send>(
+    constructor: impl Fn() -> T + Send + Sync + 'static,
+) -> Result<&'static str, NameCollision> {
+    let constructor: Constructor = Arc::new(move || Box::new(constructor()));
+    register_derived();
+    register_named::<T>(|entry| entry.constructor = Some(constructor))
+}
+
+/// Get the [`TypeId`] of the type registered with the given human-friendly name, like
+/// `"Vec<Point>"`.
+pub fn type_id_of_name(name: &str) -> Option<TypeId> {
+    register_derived();
+    NAMES
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(name)
+        .map(|entry| entry.type_id)
+}
+
+/// Build a value of the type registered with the given human-friendly name, using the
+/// constructor given to [`register_constructor`].
+pub fn construct(name: &str) -> Option<Box<dyn Any + Send>> {
+    register_derived();
+    let constructor = NAMES
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(name)?
+        .constructor
+        .clone()?;
+    Some(constructor())
+}
+
+fn register_named<T: ?Sized + 'static>(
+    update: impl FnOnce(&mut NamedType),
+) -> Result<&'static str, NameCollision> {
+    let type_id = TypeId::of::<T>();
+    let name = register::<T>();
+    let mut names = NAMES.write().unwrap_or_else(|err| err.into_inner());
+    let entry = names.entry(name).or_insert_with(|| NamedType {
+        type_id,
+        constructor: None,
+        #[cfg(feature = "serde")]
+        deserialize: BTreeMap::new(),
+    });
+    if entry.type_id != type_id {
+        return Err(NameCollision { name, existing: entry.type_id, new: type_id });
+    }
+    update(entry);
+    Ok(name)
+}
+
+#[cfg(feature = "serde")]
+type DeserializeFn<Target> = Box<
+    dyn Fn(serde_value::Value) -> Result<Box<Target>, serde_value::DeserializerError> + Send + Sync,
+>;
+
+/// Register given type `T` in both directions, so that it can be deserialized from a map
+/// tagged with its name by [`deserialize_tagged`] into a `Box<Target>`, usually a boxed
+/// trait object like `Box<dyn Policy>`.
+///
+/// The given function converts the deserialized value into the box, which is usually
+/// `|value| Box::new(value)`. A type can be registered for several target types, and registering it
+/// again for the same target type replaces the function.
+///
+/// # Errors
+/// Returns [`NameCollision`] if another type with the same name has been registered in
+/// the reverse direction.
+#[cfg(feature = "serde")]
+pub fn register_deserialize<T, Target>(
+    into_box: impl Fn(T) -> Box<Target> + Send + Sync + 'static,
+) -> Result<&'static str, NameCollision>
+where
+    T: serde::de::DeserializeOwned + 'static,
+    Target: ?Sized + 'static,
+{
+    let deserialize: DeserializeFn<Target> =
+        Box::new(move |value| Ok(into_box(value.deserialize_into::<T>()?)));
+    register_derived();
+    register_named::<T>(|entry| {
+        entry.deserialize.insert(TypeId::of::<Target>(), Arc::new(deserialize));
+    })
+}
+
+/// Deserialize a map like `{"type": "RetryPolicy", "attempts": 3}` into a `Box<Target>`
+/// holding a value of the type registered for `Target` with [`register_deserialize`]
+/// under the name in its `type` field.
+///
+/// This can be used with `#[serde(deserialize_with = "...")]`, which infers `Target` from
+/// the type of the field.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::registry;
+///
+/// trait Policy {
+///     fn attempts(&self) -> u32;
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct RetryPolicy { attempts: u32 }
+///
+/// impl Policy for RetryPolicy {
+///     fn attempts(&self) -> u32 { self.attempts }
+/// }
+///
+/// registry::register_deserialize::<RetryPolicy, dyn Policy>(|policy| Box::new(policy)).unwrap();
+///
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     #[serde(deserialize_with = "registry::deserialize_tagged")]
+///     policy: Box<dyn Policy>,
+/// }
+///
+/// let config: Config = serde_json::from_str(
+///     r#"{ "policy": { "type": "RetryPolicy", "attempts": 3 } }"#).unwrap();
+/// assert_eq!(config.policy.attempts(), 3);
+/// ```
+#[cfg(feature = "serde")]
+pub fn deserialize_tagged<'de, D, Target>(deserializer: D) -> Result<Box<Target>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    Target: ?Sized + 'static,
+{
+    use serde::Deserialize;
+    use serde::de::Error;
+    use serde_value::Value;
+
+    let Value::Map(mut map) = Value::deserialize(deserializer)? else {
+        return Err(D::Error::custom("expected a map with a `type` field"));
+    };
+    let name = match map.remove(&Value::String("type".to_owned())) {
+        Some(Value::String(name)) => name,
+        Some(_) => return Err(D::Error::custom("expected a string in the `type` field")),
+        None => return Err(D::Error::missing_field("type")),
+    };
+    register_derived();
+    let deserialize = NAMES
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(name.as_str())
+        .ok_or_else(|| D::Error::custom(format!("unknown type `{name}`")))?
+        .deserialize
+        .get(&TypeId::of::<Target>())
+        .cloned()
+        .ok_or_else(|| D::Error::custom(format!(
+            "type `{name}` is not registered for deserialization into `Box<{}>`",
+            type_name::<Target>())))?;
+    let deserialize = deserialize
+        .downcast_ref::<DeserializeFn<Target>>()
+        .expect("deserialization registered for another target type");
+    deserialize(Value::Map(map)).map_err(D::Error::custom)
+}
+
+/// Serialize the given value as a map tagged with its human-friendly name, like
+/// `{"type": "RetryPolicy", "attempts": 3}`, the format read by [`deserialize_tagged`].
+///
+/// The value must serialize as a map or a struct with named fields.
+#[cfg(feature = "serde")]
+pub fn serialize_tagged<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: serde::Serialize + ?Sized,
+    S: serde::Serializer,
+{
+    use serde::Serialize;
+    use serde::ser::Error;
+    use serde_value::Value;
+
+    let Value::Map(mut map) = serde_value::to_value(value).map_err(S::Error::custom)? else {
+        return Err(S::Error::custom("expected a value serialized as a map"));
+    };
+    let name = Value::String(type_name::<T>().to_owned());
+    map.insert(Value::String("type".to_owned()), name);
+    Value::Map(map).serialize(serializer)
+}
+
+/// Registration of a type deriving [`PrettyName`](crate::PrettyName), collected at link
+/// time.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub struct Registration(fn());
+
+#[cfg(feature = "derive")]
+impl Registration {
+    pub const fn of<T: 'static>() -> Self {
+        Self(|| {
+            // a name shared by several derived types is taken by whichever comes first
+            let _ = register_named::<T>(|_| {});
+        })
+    }
+}
+
+#[cfg(feature = "derive")]
+inventory::collect!(Registration);
+
+#[cfg(feature = "derive")]
+fn register_derived() {
+    static ONCE: std::sync::Once = std::sync::Once::new();
+    ONCE.call_once(|| {
+        for registration in inventory::iter::<Registration> {
+            (registration.0)();
+        }
+    });
+}
+
+#[cfg(not(feature = "derive"))]
+fn register_derived() {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        struct Registered;
+        struct Unregistered;
+
+        assert_eq!(register::<Registered>(), "Registered");
+        assert_eq!(register::<Registered>(), "Registered");
+        assert_eq!(register::<Vec<Option<Registered>>>(), "Vec<Option<Registered>>");
+        assert_eq!(register::<dyn Any + Send>(), "dyn Any + Send");
+
+        assert_eq!(name_of_type_id(TypeId::of::<Registered>()), Some("Registered"));
+        assert_eq!(name_of_type_id(TypeId::of::<Unregistered>()), None);
+
+        let boxed: Box<dyn Any> = Box::new(Registered);
+        assert_eq!(name_of_val(&*boxed), Some("Registered"));
+        assert_eq!(name_of_val(&Unregistered), None);
+
+        let types = registered_types();
+        assert!(types.contains(&(TypeId::of::<Registered>(), "Registered")));
+        assert!(!types.iter().any(|&(type_id, _)| type_id == TypeId::of::<Unregistered>()));
+
+        let threads = (0..8)
+            .map(|_| std::thread::spawn(register::<[Registered; 2]>))
+            .collect::<Vec<_>>();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), "[Registered; 2]");
+        }
+        assert_eq!(name_of_type_id(TypeId::of::<[Registered; 2]>()), Some("[Registered; 2]"));
+    }
+
+    #[test]
+    fn test_reverse_registry() {
+        mod a {
+            pub struct Point(pub i32);
+        }
+        mod b {
+            pub struct Point;
+        }
+
+        assert_eq!(try_register::<Vec<a::Point>>(), Ok("Vec<Point>"));
+        assert_eq!(try_register::<Vec<a::Point>>(), Ok("Vec<Point>"));
+        assert_eq!(type_id_of_name("Vec<Point>"), Some(TypeId::of::<Vec<a::Point>>()));
+        assert!(construct("Vec<Point>").is_none());
+
+        assert_eq!(register_constructor(|| a::Point(1)), Ok("Point"));
+        assert_eq!(register_constructor(|| a::Point(2)), Ok("Point"));
+        let point = construct("Point").unwrap();
+        assert_eq!(point.downcast_ref::<a::Point>().map(|point| point.0), Some(2));
+
+        let collision = try_register::<b::Point>().unwrap_err();
+        assert_eq!(collision, NameCollision {
+            name: "Point",
+            existing: TypeId::of::<a::Point>(),
+            new: TypeId::of::<b::Point>(),
+        });
+        assert_eq!(
+            collision.to_string(),
+            "another type is already registered with the name `Point`");
+        assert_eq!(type_id_of_name("Point"), Some(TypeId::of::<a::Point>()));
+
+        assert_eq!(type_id_of_name("Unregistered"), None);
+        assert!(construct("Unregistered").is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tagged_serde() {
+        trait Delay {
+            fn delay_ms(&self, attempt: u32) -> f64;
+        }
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Backoff {
+            base_ms: u64,
+            factor: f64,
+        }
+
+        impl Delay for Backoff {
+            fn delay_ms(&self, attempt: u32) -> f64 {
+                self.base_ms as f64 * self.factor.powi(attempt as i32)
+            }
+        }
+
+        #[derive(serde::Deserialize)]
+        struct Jitter {}
+
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_tagged")]
+            backoff: Box<dyn Delay>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct AnyConfig {
+            #[serde(deserialize_with = "deserialize_tagged")]
+            backoff: Box<dyn Any + Send>,
+        }
+
+        #[derive(serde::Serialize)]
+        struct ConfigOut {
+            #[serde(serialize_with = "serialize_tagged")]
+            backoff: Backoff,
+        }
+
+        assert_eq!(register_deserialize::<Backoff, dyn Delay>(|backoff| Box::new(backoff)), Ok("Backoff"));
+        assert_eq!(register_deserialize::<Backoff, dyn Any + Send>(|backoff| Box::new(backoff)), Ok("Backoff"));
+        assert_eq!(try_register::<Jitter>(), Ok("Jitter"));
+
+        let backoff = Backoff { base_ms: 100, factor: 1.5 };
+        let json = serde_json::to_string(&ConfigOut { backoff }).unwrap();
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(value, serde_json::json!({
+            "backoff": { "type": "Backoff", "base_ms": 100, "factor": 1.5 },
+        }));
+
+        let config = serde_json::from_str::<Config>(&json).unwrap();
+        assert_eq!(config.backoff.delay_ms(2), 225.0);
+        let config = serde_json::from_str::<AnyConfig>(&json).unwrap();
+        assert_eq!(
+            config.backoff.downcast_ref(),
+            Some(&Backoff { base_ms: 100, factor: 1.5 }));
+
+        for (json, error) in [
+            (r#"{ "backoff": 1 }"#, "expected a map with a `type` field"),
+            (r#"{ "backoff": { "base_ms": 1 } }"#, "missing field `type`"),
+            (r#"{ "backoff": { "type": 1 } }"#, "expected a string in the `type` field"),
+            (r#"{ "backoff": { "type": "Retry" } }"#, "unknown type `Retry`"),
+            (
+                r#"{ "backoff": { "type": "Jitter" } }"#,
+                "type `Jitter` is not registered for deserialization into `Box<dyn Delay>`",
+            ),
+            (r#"{ "backoff": { "type": "Backoff" } }"#, "Missing field base_ms"),
+        ] {
+            let err = serde_json::from_str::<Config>(json).err().unwrap().to_string();
+            assert!(err.starts_with(error), "{err}");
+        }
+    }
+}
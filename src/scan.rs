@@ -0,0 +1,68 @@
+//! Byte-level scanning of type names, shared by the truncation that does not go through
+//! `syn`. All functions are `const` so that they can be used by `const_type_name`.
+
+/// Check whether the given byte may start an identifier. Non-ASCII bytes are accepted so
+/// that multi-byte characters are never split.
+pub(crate) const fn is_ident_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || !byte.is_ascii()
+}
+
+/// Check whether the given byte may continue an identifier.
+pub(crate) const fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || !byte.is_ascii()
+}
+
+/// Scan a path like `a::b::c` starting at `start`, and get its end and the start of its
+/// last segment.
+pub(crate) const fn scan_path(bytes: &[u8], start: usize) -> (usize, usize) {
+    let mut end = start;
+    let mut last_segment = start;
+    loop {
+        while end < bytes.len() && is_ident_byte(bytes[end]) {
+            end += 1;
+        }
+        let is_separator =
+            end + 2 < bytes.len() &&
+            bytes[end] == b':' &&
+            bytes[end + 1] == b':' &&
+            is_ident_start(bytes[end + 2]);
+        if !is_separator {
+            return (end, last_segment);
+        }
+        end += 2;
+        last_segment = end;
+    }
+}
+
+/// Find the next chunk of a name from `std::any::type_name` to keep when truncating it
+/// like [`type_name`](crate::type_name), starting at `start`.
+///
+/// Every path is cut down to its last segment, and lifetimes of references are removed.
+/// The rest of the name is already laid out the way it is printed. Returns the range of
+/// the chunk to keep and the position to continue from, which is the end of the name
+/// after the last chunk.
+pub(crate) const fn next_chunk(bytes: &[u8], start: usize) -> (usize, usize, usize) {
+    let mut i = start;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if is_ident_start(byte) && (i == 0 || !is_ident_byte(bytes[i - 1])) {
+            let (end, last_segment) = scan_path(bytes, i);
+            if last_segment > i {
+                return (start, i, last_segment);
+            }
+            i = end;
+        } else if byte == b'\'' && i > 0 && bytes[i - 1] == b'&' {
+            let mut end = i + 1;
+            while end < bytes.len() && is_ident_byte(bytes[end]) {
+                end += 1;
+            }
+            if end < bytes.len() && bytes[end] == b' ' {
+                end += 1;
+            }
+            return (start, i, end);
+        } else {
+            i += 1;
+        }
+    }
+    (start, bytes.len(), bytes.len())
+}
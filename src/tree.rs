@@ -0,0 +1,230 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::display;
+
+/// Maximum line width used by [`type_name_pretty_tree`], the same as rustfmt's default.
+pub const DEFAULT_MAX_WIDTH: usize = 100;
+
+/// Get the human-friendly type name of given type `T` as an indented tree.
+///
+/// Same as [`type_name_pretty_tree_with_width`] with a maximum line width of
+/// [`DEFAULT_MAX_WIDTH`].
+pub fn type_name_pretty_tree<T: ?Sized>() -> String {
+    type_name_pretty_tree_with_width::<T>(DEFAULT_MAX_WIDTH)
+}
+
+/// Get the human-friendly type name of given type `T` as an indented tree, wrapped to at
+/// most `max_width` characters per line when possible.
+///
+/// Like rustfmt, generic arguments, tuple elements and function parameters that do not
+/// fit on the current line are broken out one per line, each followed by a comma and
+/// indented by four spaces per nesting level. Lists that fit are kept on one line, and
+/// the elements of array and slice types are broken out without a comma.
+///
+/// # Examples
+/// ```rust
+/// use pretty_name::type_name_pretty_tree_with_width;
+/// type Nested = Result<Vec<(u8, String)>, Box<dyn std::error::Error + Send + Sync>>;
+/// assert_eq!(
+///     type_name_pretty_tree_with_width::<Nested>(40),
+///     "Result<\n    Vec<(u8, String)>,\n    Box<dyn Error + Send + Sync>,\n>");
+/// assert_eq!(
+///     type_name_pretty_tree_with_width::<Nested>(100),
+///     "Result<Vec<(u8, String)>, Box<dyn Error + Send + Sync>>");
+/// ```
+pub fn type_name_pretty_tree_with_width<T: ?Sized>(max_width: usize) -> String {
+    let name = display::<T>().to_string();
+    let tree = parse_seq(&mut name.chars().peekable());
+    let mut out = String::with_capacity(name.len());
+    write_seq(&tree, 0, 0, max_width, &mut out);
+    out
+}
+
+/// A piece of a type name: either plain text, or a bracketed list of items separated by
+/// commas.
+enum Node {
+    Text(String),
+    List {
+        open: char,
+        close: char,
+        items: Vec<Vec<Node>>,
+        trailing_comma: bool,
+    },
+}
+
+/// Parse nodes until a comma or closing bracket at the current level.
+fn parse_seq(chars: &mut Peekable<Chars>) -> Vec<Node> {
+    let mut seq = Vec::new();
+    let mut text = String::new();
+    while let Some(&c) = chars.peek() {
+        let close = match c {
+            '<' => '>',
+            '(' => ')',
+            '[' => ']',
+            ',' | '>' | ')' | ']' => break,
+            '-' => {
+                chars.next();
+                text.push('-');
+                if chars.peek() == Some(&'>') {
+                    chars.next();
+                    text.push('>');
+                }
+                continue;
+            }
+            c => {
+                chars.next();
+                text.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        if !text.is_empty() {
+            seq.push(Node::Text(std::mem::take(&mut text)));
+        }
+        seq.push(parse_list(chars, c, close));
+    }
+    if !text.is_empty() {
+        seq.push(Node::Text(text));
+    }
+    seq
+}
+
+/// Parse the items of a list after its opening bracket, up to its closing bracket.
+fn parse_list(chars: &mut Peekable<Chars>, open: char, close: char) -> Node {
+    let mut items = Vec::new();
+    let mut trailing_comma = false;
+    loop {
+        let item = parse_seq(chars);
+        match chars.next() {
+            Some(',') => {
+                items.push(item);
+                if chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+                if chars.peek() == Some(&close) {
+                    chars.next();
+                    trailing_comma = true;
+                    break;
+                }
+            }
+            _ => {
+                if !item.is_empty() {
+                    items.push(item);
+                }
+                break;
+            }
+        }
+    }
+    Node::List { open, close, items, trailing_comma }
+}
+
+fn write_seq(seq: &[Node], indent: usize, suffix_len: usize, max_width: usize, out: &mut String) {
+    for (i, node) in seq.iter().enumerate() {
+        match *node {
+            Node::Text(ref text) => out.push_str(text),
+            Node::List { open, close, ref items, .. } => {
+                let line_start = out.rfind('\n').map_or(0, |i| i + 1);
+                let column = out[line_start..].chars().count();
+                let rest_len = seq[i + 1..].iter().map(flat_len).sum::<usize>() + suffix_len;
+                if items.is_empty() || column + flat_len(node) + rest_len <= max_width {
+                    write_flat(node, out);
+                    continue;
+                }
+                out.push(open);
+                for item in items {
+                    out.push('\n');
+                    push_indent(indent + 1, out);
+                    write_seq(item, indent + 1, 1, max_width, out);
+                    if open != '[' {
+                        out.push(',');
+                    }
+                }
+                out.push('\n');
+                push_indent(indent, out);
+                out.push(close);
+            }
+        }
+    }
+}
+
+fn write_flat(node: &Node, out: &mut String) {
+    match *node {
+        Node::Text(ref text) => out.push_str(text),
+        Node::List { open, close, ref items, trailing_comma } => {
+            out.push(open);
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                for node in item {
+                    write_flat(node, out);
+                }
+            }
+            if trailing_comma {
+                out.push(',');
+            }
+            out.push(close);
+        }
+    }
+}
+
+fn flat_len(node: &Node) -> usize {
+    let mut flat = String::new();
+    write_flat(node, &mut flat);
+    flat.chars().count()
+}
+
+fn push_indent(indent: usize, out: &mut String) {
+    for _ in 0..indent {
+        out.push_str("    ");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pretty_tree() {
+        type Service = Box<dyn Fn(Vec<u8>, std::collections::HashMap<String, (u32,)>) -> Result<[u8; 4], String>>;
+
+        assert_eq!(
+            type_name_pretty_tree::<Service>(),
+            "Box<dyn Fn(Vec<u8>, HashMap<String, (u32,)>) -> Result<[u8; 4], String>>");
+        assert_eq!(
+            type_name_pretty_tree_with_width::<Service>(72),
+            "Box<dyn Fn(Vec<u8>, HashMap<String, (u32,)>) -> Result<[u8; 4], String>>");
+        assert_eq!(type_name_pretty_tree_with_width::<Service>(40), [
+            "Box<",
+            "    dyn Fn(",
+            "        Vec<u8>,",
+            "        HashMap<String, (u32,)>,",
+            "    ) -> Result<[u8; 4], String>,",
+            ">",
+        ].join("\n"));
+        assert_eq!(type_name_pretty_tree_with_width::<Service>(0), [
+            "Box<",
+            "    dyn Fn(",
+            "        Vec<",
+            "            u8,",
+            "        >,",
+            "        HashMap<",
+            "            String,",
+            "            (",
+            "                u32,",
+            "            ),",
+            "        >,",
+            "    ) -> Result<",
+            "        [",
+            "            u8; 4",
+            "        ],",
+            "        String,",
+            "    >,",
+            ">",
+        ].join("\n"));
+
+        assert_eq!(type_name_pretty_tree_with_width::<()>(0), "()");
+        assert_eq!(type_name_pretty_tree_with_width::<fn()>(0), "fn()");
+    }
+}
@@ -1,4 +1,7 @@
-use syn::*;
+use syn::Type;
+
+use crate::print::print_type;
+use crate::truncate::truncate_type;
 
 /// Get the human-friendly type name of given type `T`.
 /// 
@@ -50,128 +53,29 @@ pub fn type_name_of_val<T: ?Sized>(_: &T) -> &'static str {
 
 fn type_name_internal<T: ?Sized>() -> &'static str {
     let type_name = std::any::type_name::<T>();
-    let Ok(mut ty) = syn::parse_str::<Type>(type_name) else {
-        return "<error>";
-    };
-
-    truncate_type(&mut ty);
-
-    // Use rustfmt to get a nicely formatted type string.
-    // rustfmt only accepts full source files, so we wrap the type in a dummy function.
-    use quote::quote;
-    use rust_format::Formatter as _;
-    let format_result =
-        rust_format::RustFmt::default()
-            .format_tokens(quote!(fn main() -> #ty {}))
-            .unwrap_or("<error>".to_string());
-    let start = const { "fn main() -> ".len() };
-    let end = format_result.len() - const { " {}\r\n".len() };
     Box::leak(
-        format_result[start..end]
-            .to_owned()
+        truncate_type_name(type_name)
             .into_boxed_str())
 }
 
-fn truncate_type(ty: &mut Type) {
-    match *ty {
-        Type::Infer(_) |
-        Type::Macro(_) |
-        Type::Never(_) |
-        Type::Verbatim(_) => {}
-
-        Type::Array(TypeArray { ref mut elem, .. }) |
-        Type::Group(TypeGroup { group_token: _, ref mut elem }) |
-        Type::Paren(TypeParen { paren_token: _, ref mut elem }) |
-        Type::Ptr(TypePtr { ref mut elem, .. }) |
-        Type::Slice(TypeSlice { ref mut elem, .. }) => truncate_type(elem),
-
-        Type::Reference(TypeReference {
-            ref mut lifetime,
-            ref mut elem,
-            ..
-        }) => {
-            *lifetime = None;
-            truncate_type(elem);
-        }
-
-        Type::Path(ref mut ty) => truncate_path(&mut ty.path),
-
-        Type::BareFn(ref mut ty) => {
-            for input in ty.inputs.iter_mut() {
-                truncate_type(&mut input.ty);
-            }
-
-            if let ReturnType::Type(_, ref mut ty) = ty.output {
-                truncate_type(ty.as_mut());
-            }
-        }
-
-        Type::ImplTrait(ref mut ty) => {
-            for bound in ty.bounds.iter_mut() {
-                if let &mut TypeParamBound::Trait(ref mut trt) = bound {
-                    truncate_path(&mut trt.path);
-                }
-            }
-        }
-
-        Type::TraitObject(ref mut ty) => {
-            for bound in ty.bounds.iter_mut() {
-                if let &mut TypeParamBound::Trait(ref mut trt) = bound {
-                    truncate_path(&mut trt.path);
-                }
-            }
-        }
-
-        Type::Tuple(ref mut ty) => {
-            for elem in ty.elems.iter_mut() {
-                truncate_type(elem);
-            }
-        }
-
-        _ => { /* non_exhaustive variants */ }
-    }
-}
-
-fn truncate_path(path: &mut Path) {
-    let path_mut = path;
-    let path = std::mem::replace(
-        path_mut,
-        Path {
-            leading_colon: None,
-            segments: Default::default(),
-        });
-
-    let Some(mut last_segment) = path.segments.into_iter().last() else {
-        path_mut.leading_colon = None;
-        path_mut.segments = Default::default();
-        return;
+/// Truncate the given type name with the same rules as [`type_name`].
+///
+/// Used by macros that only have the tokens of a type or trait path at hand.
+#[doc(hidden)]
+pub fn truncate_type_name(type_name: &str) -> String {
+    let Ok(mut ty) = syn::parse_str::<Type>(type_name) else {
+        return "<error>".to_owned();
     };
 
-    match last_segment.arguments {
-        PathArguments::None => {}
-        PathArguments::AngleBracketed(ref mut args) => {
-            for arg in args.args.iter_mut() {
-                match *arg {
-                    GenericArgument::Type(ref mut ty) => truncate_type(ty),
-                    GenericArgument::AssocType(ref mut ty) => {
-                        truncate_type(&mut ty.ty)
-                    }
-                    _ => {}
-                }
-            }
-        }
-        PathArguments::Parenthesized(ref mut args) => {
-            for input in args.inputs.iter_mut() {
-                truncate_type(input);
-            }
-            if let ReturnType::Type(_, ref mut output) = args.output {
-                truncate_type(output);
-            }
-        }
-    }
+    truncate_type(&mut ty, false);
+    format_type(&ty)
+}
 
-    path_mut.leading_colon = None;
-    path_mut.segments = Some(last_segment).into_iter().collect();
+/// Format the given type on a single line in the layout rustfmt uses.
+pub(crate) fn format_type(ty: &Type) -> String {
+    let mut out = String::new();
+    print_type(ty, &mut out);
+    out
 }
 
 #[cfg(test)]
@@ -307,10 +211,55 @@ mod test {
         assert_eq!(type_name::<Vec<Option<Result<Box<dyn std::fmt::Debug>, String>>>>(), "Vec<Option<Result<Box<dyn Debug>, String>>>");
         assert_eq!(type_name::<&[Option<&[(i32, &str)]>]>(), "&[Option<&[(i32, &str)]>]");
         assert_eq!(type_name::<fn(Vec<&str>) -> Option<Result<i32, Box<dyn std::error::Error>>>>(), "fn(Vec<&str>) -> Option<Result<i32, Box<dyn Error>>>");
+        // Longer than rustfmt's default line width
+        assert_eq!(type_name::<Vec<(Option<std::collections::HashMap<String, Vec<u8>>>, Result<Box<dyn std::error::Error + Send + Sync>, std::io::Error>)>>(), "Vec<(Option<HashMap<String, Vec<u8>>>, Result<Box<dyn Error + Send + Sync>, Error>)>");
 
         // ===== Edge Cases =====
         assert_eq!(type_name::<[(); 5]>(), "[(); 5]");
         assert_eq!(type_name::<std::marker::PhantomData<i32>>(), "PhantomData<i32>");
         assert_eq!(type_name::<std::marker::PhantomData<&str>>(), "PhantomData<&str>");
     }
+
+    /// The printer must lay out every kind of type syntax exactly like rustfmt, which
+    /// formatted type names before.
+    #[test]
+    fn test_format_type_matches_rustfmt() {
+        use rust_format::Formatter as _;
+        use super::format_type;
+
+        let config = rust_format::Config::new_str().option("max_width", "100000");
+        let rustfmt = rust_format::RustFmt::from_config(config);
+        for source in [
+            "i32", "str", "[u8]", "&'a str", "&'static mut [i32]", "*const *mut i32",
+            "[i32; 5]", "[[u8; 2]; N]", "[u8; 2 + 2]", "()", "(i32,)", "(i32, (String, bool))",
+            "Option<&str>", "Result<(), ()>", "HashMap<String, Vec<i32>>", "Cow<'static, str>",
+            "::std::vec::Vec<u8>", "Vec::<u8>", "<T as Iterator>::Item", "<Vec<T>>::IntoIter",
+            "Foo<'a, T, 3, { N + 1 }>", "Box<dyn Iterator<Item = u8>>",
+            "Box<dyn Fn(u8, &str) -> Vec<u8>>", "&mut dyn FnMut()", "fn()", "fn(&str) -> String",
+            "fn(x: u8, _: u16)", "fn(u8, ...)",
+            "for<'a> fn(&'a u8) -> &'a u8", "unsafe extern \"C\" fn(i32)",
+            "extern fn()", "fn() -> fn(i32) -> i32", "fn() -> !", "!", "_",
+            "dyn Debug", "dyn Debug + Send + Sync + 'static", "&dyn Fn(u8) -> u8",
+            "&(dyn Debug + Send)", "Box<dyn for<'a> Fn(&'a u8) + ?Sized>", "dyn ?Sized + Trait",
+            "impl Iterator<Item = Vec<u8>> + 'a", "impl Fn() -> u8 + use<'a, T>",
+            "(dyn Debug)", "&(u8)", "Self", "Option<Self>", "&'a mut T", "dyn Trait<'a>",
+            "Foo<-1>", "Foo<{ N }>", "Foo<N = 3>", "Box<dyn Iterator<Item: Debug + 'a>>",
+            "Box<dyn FnOnce(u8) -> u8 + Send>", "&'static dyn Error", "impl ?Sized + Debug",
+            "for<'a, 'b> fn(&'a u8, &'b u8)", "dyn for<'a> Fn(&'a u8) -> &'a u8 + Send",
+            "fn(a: u8, ...)", "extern \"system\" fn()", "unsafe extern fn()",
+            "[u8; usize::MAX]", "[u8; { 1 + 2 }]", "[u8; (N - 1) * 2]", "Foo<{ -N }, true, 'x'>",
+            "Foo<{ N::<u8> }>", "Foo<\"str\">", "<T>::Assoc", "<T as a::Trait<u8>>::Assoc<'a>",
+            "Vec<Option<Result<Box<dyn Debug>, String>>>",
+            "Vec<(Option<HashMap<String, Vec<u8>>>, Result<Box<dyn Error + Send + Sync>, Error>)>",
+        ] {
+            let ty: syn::Type = syn::parse_str(source).unwrap_or_else(|e| panic!("{source}: {e}"));
+            let formatted = rustfmt.format_str(format!("fn main() -> {source} {{}}")).unwrap();
+            let expected = formatted
+                .trim_end()
+                .strip_prefix("fn main() -> ")
+                .and_then(|rest| rest.strip_suffix(" {}"))
+                .unwrap();
+            assert_eq!(format_type(&ty), expected, "{source}");
+        }
+    }
 }
@@ -0,0 +1,91 @@
+//! End-to-end tests of the `pretty-name` binary against the files in `tests/fixtures`.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn fixture(name: &str) -> String {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn read_fixture(name: &str) -> String {
+    std::fs::read_to_string(fixture(name)).unwrap()
+}
+
+fn run(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_pretty-name"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut child_stdin = child.stdin.take().unwrap();
+    child_stdin.write_all(stdin.unwrap_or_default().as_bytes()).unwrap();
+    drop(child_stdin);
+    child.wait_with_output().unwrap()
+}
+
+fn run_ok(args: &[&str], stdin: Option<&str>) -> String {
+    let output = run(args, stdin);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_files() {
+    for name in ["cargo_test", "backtrace"] {
+        let input = fixture(&format!("{name}.txt"));
+        assert_eq!(run_ok(&[&input], None), read_fixture(&format!("{name}.expected")));
+    }
+}
+
+#[test]
+fn test_stdin() {
+    let input = read_fixture("log.jsonl");
+    assert_eq!(run_ok(&[], Some(&input)), read_fixture("log.expected"));
+    assert_eq!(run_ok(&["-"], Some(&input)), read_fixture("log.expected"));
+}
+
+#[test]
+fn test_multiple_files() {
+    let log = fixture("log.jsonl");
+    let backtrace = fixture("backtrace.txt");
+    assert_eq!(
+        run_ok(&[&log, &backtrace], None),
+        read_fixture("log.expected") + &read_fixture("backtrace.expected"));
+}
+
+#[test]
+fn test_keep_crate() {
+    assert_eq!(
+        run_ok(&["--keep-crate", &fixture("log.jsonl")], None),
+        read_fixture("log.keep_crate.expected"));
+}
+
+#[test]
+fn test_max_len() {
+    let input = fixture("cargo_test.txt");
+    assert_eq!(run_ok(&["--max-len", "12", &input], None), read_fixture("cargo_test.max_len.expected"));
+    assert_eq!(run_ok(&["--max-len=12", &input], None), read_fixture("cargo_test.max_len.expected"));
+}
+
+#[test]
+fn test_diff() {
+    assert_eq!(
+        run_ok(&["--diff", &fixture("cargo_test.txt")], None),
+        read_fixture("cargo_test.diff.expected"));
+}
+
+#[test]
+fn test_errors() {
+    assert!(run_ok(&["--help"], None).starts_with("Usage: pretty-name"));
+    assert_eq!(run(&["--bogus"], None).status.code(), Some(2));
+    assert_eq!(run(&["--max-len", "x"], None).status.code(), Some(2));
+    assert_eq!(run(&["--max-len"], None).status.code(), Some(2));
+    assert_eq!(run(&[&fixture("missing.txt")], None).status.code(), Some(1));
+}
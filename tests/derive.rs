@@ -0,0 +1,36 @@
+//! Tests of automatic registration with `#[derive(PrettyName)]`.
+
+use std::any::TypeId;
+
+use pretty_name::registry;
+
+#[allow(dead_code)]
+#[derive(pretty_name::PrettyName)]
+struct Velocity(f32, f32);
+
+#[allow(dead_code)]
+#[derive(pretty_name::PrettyName)]
+enum Shape {
+    Circle,
+    Square,
+}
+
+#[allow(dead_code)]
+#[derive(pretty_name::PrettyName)]
+struct Wrapper<T>(T);
+
+#[test]
+fn test_derive() {
+    // the reverse lookup registers derived types by itself
+    assert_eq!(registry::type_id_of_name("Velocity"), Some(TypeId::of::<Velocity>()));
+    assert_eq!(registry::type_id_of_name("Shape"), Some(TypeId::of::<Shape>()));
+    assert_eq!(registry::type_id_of_name("Wrapper<u8>"), None);
+
+    assert_eq!(registry::name_of_type_id(TypeId::of::<Velocity>()), Some("Velocity"));
+    assert_eq!(registry::name_of_val(&Shape::Circle), Some("Shape"));
+    assert_eq!(registry::name_of_type_id(TypeId::of::<Wrapper<u8>>()), None);
+
+    let types = registry::registered_types();
+    assert!(types.contains(&(TypeId::of::<Velocity>(), "Velocity")));
+    assert!(types.contains(&(TypeId::of::<Shape>(), "Shape")));
+}